@@ -0,0 +1,259 @@
+//! An in-game console for spawning enemies, cheating and moving around in the script.
+//!
+//! Commands go through the same events and script queue as the rest of the game, so what they do
+//! matches what happens while playing.
+
+use std::str::FromStr;
+
+use bevy::{
+    input::{InputSystem, common_conditions::input_just_pressed},
+    prelude::*,
+};
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+use crate::{
+    enemy::{boss::BossController, configs::BOSS_PHASES},
+    health::{ChangeHpEvent, Health, Invulnerable, Shield},
+    player::character::Player,
+    script::script::{Dialogue, Enemy, ResetScript, ScriptEvent, ScriptEventQueue, ScriptPosition},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Console>();
+    app.add_event::<ConsoleCommand>();
+    app.add_systems(
+        PreUpdate,
+        (
+            toggle_console.run_if(input_just_pressed(TOGGLE_KEY)),
+            hide_keys_from_game.run_if(|console: Res<Console>| console.open),
+        )
+            .chain()
+            .after(InputSystem),
+    );
+    app.add_systems(EguiContextPass, console_window);
+    app.add_systems(
+        Update,
+        run_console_commands.run_if(on_event::<ConsoleCommand>),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F1;
+
+const HELP: &str = "\
+spawn <enemy> <x> <y>   enemies: boss, black_slime, red_slime, splitting_slime,
+                        bouncer_slime, armored_slime, imp, wisp, caster
+god                     toggle player invulnerability
+heal                    refill the player's health
+kill_all                kill every enemy
+script skip             drop the current script event
+script goto <label/index>
+                        labels: intro, asad, red_slimes, swarm, boss, ending
+timescale <scale>       1 is normal speed
+boss phase <phase>      hurt the boss into a phase, counting from 1";
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+}
+
+#[derive(Event, Debug)]
+enum ConsoleCommand {
+    Spawn(Enemy, Vec2),
+    God,
+    Heal,
+    KillAll,
+    ScriptSkip,
+    ScriptGoTo(ScriptPosition),
+    TimeScale(f32),
+    BossPhase(usize),
+    Help,
+}
+
+impl FromStr for ConsoleCommand {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |word: &str| {
+            word.parse::<f32>()
+                .map_err(|_| format!("'{word}' isn't a number"))
+        };
+        match words.as_slice() {
+            ["spawn", enemy, x, y] => Ok(Self::Spawn(
+                parse_enemy(enemy)?,
+                Vec2::new(number(x)?, number(y)?),
+            )),
+            ["god"] => Ok(Self::God),
+            ["heal"] => Ok(Self::Heal),
+            ["kill_all"] => Ok(Self::KillAll),
+            ["script", "skip"] => Ok(Self::ScriptSkip),
+            ["script", "goto", position] => {
+                let Ok(position) = position.parse::<ScriptPosition>();
+                Ok(Self::ScriptGoTo(position))
+            }
+            ["timescale", scale] => match number(scale)? {
+                scale if scale > 0.0 => Ok(Self::TimeScale(scale)),
+                _ => Err("the time scale has to be positive".to_string()),
+            },
+            ["boss", "phase", phase] => match phase.parse() {
+                Ok(phase) if (1..=BOSS_PHASES.len()).contains(&phase) => Ok(Self::BossPhase(phase)),
+                _ => Err(format!("the boss has phases 1 to {}", BOSS_PHASES.len())),
+            },
+            ["help"] => Ok(Self::Help),
+            _ => Err(format!("unknown command '{line}', try 'help'")),
+        }
+    }
+}
+
+fn parse_enemy(name: &str) -> Result<Enemy, String> {
+    match name {
+        "boss" => Ok(Enemy::Boss),
+        "black_slime" => Ok(Enemy::BlackSlime),
+        "red_slime" => Ok(Enemy::RedSlime),
+        "splitting_slime" => Ok(Enemy::SplittingSlime),
+        "bouncer_slime" => Ok(Enemy::BouncerSlime),
+        "armored_slime" => Ok(Enemy::ArmoredSlime),
+        "imp" => Ok(Enemy::Imp),
+        "wisp" => Ok(Enemy::Wisp),
+        "caster" => Ok(Enemy::Caster),
+        _ => Err(format!("unknown enemy '{name}'")),
+    }
+}
+
+fn toggle_console(mut console: ResMut<Console>) {
+    console.open = !console.open;
+}
+
+/// Keeps what's typed into the console from also moving the player or advancing dialogue.
+fn hide_keys_from_game(mut keys: ResMut<ButtonInput<KeyCode>>) {
+    keys.reset_all();
+}
+
+fn console_window(
+    mut contexts: EguiContexts,
+    mut console: ResMut<Console>,
+    mut console_commands: EventWriter<ConsoleCommand>,
+) {
+    if !console.open {
+        return;
+    }
+    let console = console.into_inner();
+    let mut open = true;
+    egui::Window::new("Console")
+        .open(&mut open)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in &console.history {
+                        ui.monospace(line);
+                    }
+                });
+            let input = ui.text_edit_singleline(&mut console.input);
+            if input.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                let line = std::mem::take(&mut console.input);
+                console.history.push(format!("> {line}"));
+                match line.parse() {
+                    Ok(command) => {
+                        console_commands.write(command);
+                    }
+                    Err(error) => console.history.push(error),
+                }
+            }
+            input.request_focus();
+        });
+    console.open &= open;
+}
+
+fn run_console_commands(
+    mut console_commands: EventReader<ConsoleCommand>,
+    mut console: ResMut<Console>,
+    mut commands: Commands,
+    mut script_events: ResMut<ScriptEventQueue>,
+    mut resets: EventWriter<ResetScript>,
+    mut change_hp: EventWriter<ChangeHpEvent>,
+    mut time: ResMut<Time<Virtual>>,
+    player: Query<(Entity, &Health, Has<Invulnerable>), With<Player>>,
+    enemies: Query<(Entity, &Health, Option<&Shield>), Without<Player>>,
+    boss: Query<(Entity, &BossController, &Health, Has<Invulnerable>)>,
+    mut dialogue: Query<&mut Visibility, With<Dialogue>>,
+) {
+    for command in console_commands.read() {
+        let reply = match command {
+            ConsoleCommand::Spawn(enemy, position) => {
+                script_events
+                    .queue
+                    .push_front(ScriptEvent::Spawn(*enemy, *position, &[]));
+                format!("Spawning {enemy:?} at {position}")
+            }
+            ConsoleCommand::God => match player.single() {
+                Ok((entity, _, true)) => {
+                    commands.entity(entity).remove::<Invulnerable>();
+                    "God mode off".to_string()
+                }
+                Ok((entity, _, false)) => {
+                    commands.entity(entity).insert(Invulnerable);
+                    "God mode on".to_string()
+                }
+                Err(_) => "There's no player".to_string(),
+            },
+            ConsoleCommand::Heal => match player.single() {
+                Ok((entity, health, _)) => {
+                    change_hp.write(ChangeHpEvent::new(entity, health.max - health.current));
+                    "Healed".to_string()
+                }
+                Err(_) => "There's no player".to_string(),
+            },
+            ConsoleCommand::KillAll => {
+                for (entity, health, shield) in &enemies {
+                    let shield = shield.map_or(0.0, |shield| shield.remaining);
+                    change_hp.write(ChangeHpEvent::new(entity, -(health.current + shield)));
+                }
+                format!("Killing {} enemies", enemies.iter().len())
+            }
+            ConsoleCommand::ScriptSkip => match script_events.skip() {
+                Some(event) => {
+                    // Shown again if the next event is a dialogue too.
+                    for mut visibility in &mut dialogue {
+                        *visibility = Visibility::Hidden;
+                    }
+                    format!("Skipped {event:?}")
+                }
+                None => "The script is already over".to_string(),
+            },
+            ConsoleCommand::ScriptGoTo(position) => match position.index() {
+                Some(index) => {
+                    resets.write(ResetScript {
+                        start_at: position.clone(),
+                        ..default()
+                    });
+                    format!("Going to script event {index}")
+                }
+                None => format!("There's no {position:?} in the script"),
+            },
+            ConsoleCommand::TimeScale(scale) => {
+                time.set_relative_speed(*scale);
+                format!("Time scale set to {scale}")
+            }
+            ConsoleCommand::BossPhase(phase) => match boss.single() {
+                Ok((_, controller, _, _)) if controller.phase_number() >= *phase => {
+                    format!("The boss is already in phase {}", controller.phase_number())
+                }
+                Ok((_, _, _, true)) => "The boss can't be hurt right now".to_string(),
+                Ok((entity, _, health, false)) => {
+                    // Hurting the boss to the threshold lets it change phase the usual way.
+                    let threshold = BOSS_PHASES[phase - 1].health_threshold * health.max;
+                    change_hp.write(ChangeHpEvent::new(entity, threshold - health.current));
+                    format!("Hurting the boss into phase {phase}")
+                }
+                Err(_) => "There's no boss".to_string(),
+            },
+            ConsoleCommand::Help => HELP.to_string(),
+        };
+        info!("Console: {reply}");
+        console.history.push(reply);
+    }
+}
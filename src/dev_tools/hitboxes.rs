@@ -0,0 +1,153 @@
+//! Overlay that color-codes [`HitBox`]es and [`HurtBox`]es and labels them with their damage and
+//! timers. Builds on the colliders drawn by `PhysicsDebugPlugin`.
+
+use avian2d::prelude::{ColliderAabb, ColliderDisabled, DebugRender};
+use bevy::{
+    input::common_conditions::input_just_pressed, platform::collections::HashSet, prelude::*,
+};
+
+use crate::health::{HitBox, HurtBox};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<HitboxOverlay>();
+    app.add_systems(
+        Update,
+        (
+            toggle_overlay.run_if(input_just_pressed(TOGGLE_KEY)),
+            (color_boxes, spawn_labels, update_labels)
+                .chain()
+                .run_if(|overlay: Res<HitboxOverlay>| overlay.enabled),
+        )
+            .chain(),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F4;
+
+const HIT_BOX_COLOR: Color = Color::srgb(1.0, 0.25, 0.2);
+const HURT_BOX_COLOR: Color = Color::srgb(0.2, 0.9, 0.35);
+const DISABLED_COLOR: Color = Color::srgb(0.45, 0.45, 0.45);
+
+/// Distance between the top of a box and its label.
+const LABEL_OFFSET: f32 = 30.0;
+
+#[derive(Resource, Default)]
+struct HitboxOverlay {
+    enabled: bool,
+}
+
+/// Shows the damage and timers of the hit or hurt box it points to.
+#[derive(Component)]
+struct BoxLabel(Entity);
+
+type AnyBox = Or<(With<HitBox>, With<HurtBox>)>;
+
+fn toggle_overlay(
+    mut commands: Commands,
+    mut overlay: ResMut<HitboxOverlay>,
+    boxes: Query<Entity, (AnyBox, With<DebugRender>)>,
+    labels: Query<Entity, With<BoxLabel>>,
+) {
+    overlay.enabled = !overlay.enabled;
+    if overlay.enabled {
+        return;
+    }
+    for entity in &boxes {
+        commands.entity(entity).remove::<DebugRender>();
+    }
+    for label in &labels {
+        commands.entity(label).despawn();
+    }
+}
+
+fn box_color(is_hit_box: bool, disabled: bool) -> Color {
+    if disabled {
+        DISABLED_COLOR
+    } else if is_hit_box {
+        HIT_BOX_COLOR
+    } else {
+        HURT_BOX_COLOR
+    }
+}
+
+fn color_boxes(
+    mut commands: Commands,
+    boxes: Query<
+        (
+            Entity,
+            Has<HitBox>,
+            Has<ColliderDisabled>,
+            Option<&DebugRender>,
+        ),
+        AnyBox,
+    >,
+) {
+    for (entity, is_hit_box, disabled, render) in &boxes {
+        let color = box_color(is_hit_box, disabled);
+        if render.is_none_or(|render| render.collider_color != Some(color)) {
+            commands
+                .entity(entity)
+                .insert(DebugRender::default().with_collider_color(color));
+        }
+    }
+}
+
+fn spawn_labels(mut commands: Commands, boxes: Query<Entity, AnyBox>, labels: Query<&BoxLabel>) {
+    let labelled: HashSet<Entity> = labels.iter().map(|label| label.0).collect();
+    for entity in boxes.iter().filter(|entity| !labelled.contains(entity)) {
+        commands.spawn((
+            Name::new("Hitbox Label"),
+            BoxLabel(entity),
+            Text2d::default(),
+            TextFont::from_font_size(24.0),
+            Transform::default(),
+        ));
+    }
+}
+
+fn update_labels(
+    mut commands: Commands,
+    boxes: Query<(
+        &ColliderAabb,
+        Option<&HitBox>,
+        Option<&HurtBox>,
+        Has<ColliderDisabled>,
+    )>,
+    mut labels: Query<(
+        Entity,
+        &BoxLabel,
+        &mut Text2d,
+        &mut TextColor,
+        &mut Transform,
+    )>,
+) {
+    for (label, target, mut text, mut color, mut transform) in &mut labels {
+        let Ok((aabb, hit_box, hurt_box, disabled)) = boxes.get(target.0) else {
+            commands.entity(label).despawn();
+            continue;
+        };
+        text.0 = match (hit_box, hurt_box) {
+            (Some(hit_box), _) => {
+                let mut line = format!("dmg {:.0}", hit_box.damage());
+                for delay in hit_box.remaining_rehit_delays.values() {
+                    if *delay > 0.0 {
+                        line += &format!(" rehit {delay:.2}");
+                    }
+                }
+                line
+            }
+            (None, Some(hurt_box)) => {
+                let immunity = hurt_box.remaining_immunity_duration();
+                if immunity > 0.0 {
+                    format!("immune {immunity:.2}")
+                } else {
+                    "hurt".to_string()
+                }
+            }
+            (None, None) => String::new(),
+        };
+        color.0 = box_color(hit_box.is_some(), disabled);
+        let top = Vec2::new((aabb.min.x + aabb.max.x) / 2.0, aabb.max.y);
+        transform.translation = (top + Vec2::Y * LABEL_OFFSET).extend(50.0);
+    }
+}
@@ -0,0 +1,80 @@
+//! Command line options for jumping straight into part of the script, e.g.
+//! `cargo run -- --start-at boss --skip-dialogue`. The `START_AT` and `SKIP_DIALOGUE`
+//! environment variables do the same, for when passing arguments is awkward.
+//!
+//! Either option skips the splash, title and story screens.
+
+use bevy::prelude::*;
+
+use crate::{
+    screens::Screen,
+    script::script::{ResetScript, ScriptPosition},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    let options = LaunchOptions::from_env();
+    if !options.skips_menus() {
+        return;
+    }
+    if options
+        .start_at
+        .as_ref()
+        .is_some_and(|position| position.index().is_none())
+    {
+        warn!(
+            "Can't start at {:?}, it isn't in the script",
+            options.start_at
+        );
+    }
+    app.insert_resource(options);
+    app.add_systems(Startup, enter_loading_screen);
+    app.add_systems(OnEnter(Screen::Story), enter_gameplay_screen);
+    app.add_systems(OnEnter(Screen::Gameplay), fast_forward_script);
+}
+
+#[derive(Resource, Default, Debug)]
+struct LaunchOptions {
+    start_at: Option<ScriptPosition>,
+    skip_dialogue: bool,
+}
+
+impl LaunchOptions {
+    fn from_env() -> Self {
+        let parse = |position: String| {
+            let Ok(position) = position.parse::<ScriptPosition>();
+            position
+        };
+        let mut options = Self {
+            start_at: std::env::var("START_AT").ok().map(parse),
+            skip_dialogue: std::env::var_os("SKIP_DIALOGUE").is_some(),
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--start-at" => options.start_at = args.next().map(parse),
+                "--skip-dialogue" => options.skip_dialogue = true,
+                _ => {}
+            }
+        }
+        options
+    }
+
+    fn skips_menus(&self) -> bool {
+        self.start_at.is_some() || self.skip_dialogue
+    }
+}
+
+fn enter_loading_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
+
+fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Gameplay);
+}
+
+fn fast_forward_script(options: Res<LaunchOptions>, mut resets: EventWriter<ResetScript>) {
+    resets.write(ResetScript {
+        start_at: options.start_at.clone().unwrap_or_default(),
+        skip_dialogue: options.skip_dialogue,
+    });
+}
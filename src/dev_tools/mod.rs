@@ -1,5 +1,10 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
+mod console;
+mod hitboxes;
+mod launch;
+mod tuning_panel;
+
 use bevy::{
     dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
     ui::UiDebugOptions,
@@ -8,6 +13,13 @@ use bevy::{
 use crate::screens::Screen;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        console::plugin,
+        hitboxes::plugin,
+        launch::plugin,
+        tuning_panel::plugin,
+    ));
+
     // Log `Screen` state transitions.
     app.add_systems(Update, log_transitions::<Screen>);
 
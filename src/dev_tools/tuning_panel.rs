@@ -0,0 +1,129 @@
+//! An egui window for tuning player movement and the weapon's fury while playing.
+
+use std::{ops::RangeInclusive, path::Path};
+
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContextPass, EguiContexts, egui};
+
+use crate::tuning::{TUNING_PATH, Tuning};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(EguiContextPass, tuning_window);
+}
+
+fn tuning_window(mut contexts: EguiContexts, mut tuning: ResMut<Tuning>) {
+    // Edit a copy, so the resource only counts as changed when a value actually moves.
+    let mut edited = tuning.clone();
+    egui::Window::new("Tuning")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let player = &mut edited.player;
+            ui.heading("Movement");
+            slider(
+                ui,
+                "Movement speed",
+                &mut player.movement_speed,
+                100.0..=1500.0,
+            );
+            slider(ui, "Jump impulse", &mut player.jump_impulse, 300.0..=2500.0);
+            slider(ui, "Gravity scale", &mut player.gravity_scale, 0.1..=5.0);
+            slider(
+                ui,
+                "Jump gravity scale",
+                &mut player.jump_gravity_scale,
+                0.1..=5.0,
+            );
+            slider(
+                ui,
+                "Dash speed modifier",
+                &mut player.dash_speed_modifier,
+                1.0..=5.0,
+            );
+            slider(ui, "Dash duration", &mut player.dash_duration, 0.05..=1.0);
+            slider(ui, "Dash cooldown", &mut player.dash_cooldown, 0.0..=2.0);
+            slider(ui, "Coyote time", &mut player.coyote_time, 0.0..=1.0);
+
+            let attack = &mut edited.attack;
+            ui.heading("Fury");
+            slider(
+                ui,
+                "Initial cooldown",
+                &mut attack.initial_cooldown,
+                0.05..=5.0,
+            );
+            slider(
+                ui,
+                "Minimum cooldown",
+                &mut attack.minimum_cooldown,
+                0.01..=1.0,
+            );
+            slider(ui, "Attack period", &mut attack.attack_period, 0.1..=5.0);
+            slider(ui, "Grace period", &mut attack.grace_period, 0.1..=5.0);
+            slider(
+                ui,
+                "Initial extend scale",
+                &mut attack.initial_extend_scale,
+                1.0..=15.0,
+            );
+            slider(
+                ui,
+                "Minimum extend scale",
+                &mut attack.minimum_extend_scale,
+                0.1..=5.0,
+            );
+            slider(
+                ui,
+                "Scale increase",
+                &mut attack.scale_increase_factor,
+                1.0..=3.0,
+            );
+            slider(
+                ui,
+                "Scale decrease",
+                &mut attack.scale_decrease_factor,
+                0.1..=1.0,
+            );
+            slider(
+                ui,
+                "Cooldown increase",
+                &mut attack.cooldown_increase_factor,
+                1.0..=3.0,
+            );
+            slider(
+                ui,
+                "Cooldown decrease",
+                &mut attack.cooldown_decrease_factor,
+                0.1..=1.0,
+            );
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Reset to defaults").clicked() {
+                    edited = Tuning::default();
+                }
+                if ui.button("Export").clicked() {
+                    export(&edited);
+                }
+            });
+        });
+    tuning.set_if_neq(edited);
+}
+
+fn slider(ui: &mut egui::Ui, label: &str, value: &mut f32, range: RangeInclusive<f32>) {
+    ui.add(egui::Slider::new(value, range).text(label));
+}
+
+/// Overwrites the tuning file in the source tree, which the file watcher then reloads.
+fn export(tuning: &Tuning) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(TUNING_PATH);
+    let result = tuning
+        .to_ron()
+        .map_err(|error| error.to_string())
+        .and_then(|ron| std::fs::write(&path, ron).map_err(|error| error.to_string()));
+    match result {
+        Ok(()) => info!("Exported tuning to {}", path.display()),
+        Err(error) => error!("Couldn't export tuning to {}: {error}", path.display()),
+    }
+}
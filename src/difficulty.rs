@@ -0,0 +1,134 @@
+//! How hard the game is, picked from the main menu before a run starts.
+//!
+//! The balance constants all describe [`Difficulty::Normal`]. Other difficulties scale them
+//! through [`DifficultyScaling`] where they're used, instead of having their own copies.
+
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{
+        configs::{ATTACK_TOKENS, ENEMY_SEPARATION_RADIUS, ENEMY_SEPARATION_SPEED},
+        crowd::CrowdSettings,
+    },
+    health::Health,
+    player::character::Player,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Difficulty>();
+    app.init_resource::<Difficulty>();
+    app.add_systems(
+        Update,
+        (
+            scale_enemy_health,
+            apply_crowd_settings.run_if(resource_changed::<Difficulty>),
+        ),
+    );
+}
+
+#[derive(Resource, Reflect, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    /// For people who are here for the story.
+    Story,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+/// Multipliers applied on top of the balance constants.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DifficultyScaling {
+    pub enemy_health: f32,
+    /// Damage the player takes from enemies.
+    pub enemy_damage: f32,
+    /// How fast enemies get through their windups and cooldowns.
+    pub ai_speed: f32,
+    /// How many more sky lazers rain down during the boss fight.
+    pub sky_lazer_density: f32,
+    /// How long the player stays immune after getting hit.
+    pub player_immunity: f32,
+    /// Overrides [`CrowdSettings::attack_tokens`].
+    pub attack_tokens: usize,
+    /// How far apart ground enemies keep. Lower lets them bunch up around the player.
+    pub separation_radius: f32,
+    /// How quickly overlapping ground enemies push apart.
+    pub separation_speed: f32,
+}
+
+impl Difficulty {
+    pub const ALL: [Self; 4] = [Self::Story, Self::Normal, Self::Hard, Self::Nightmare];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Story => "Story",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Nightmare => "Nightmare",
+        }
+    }
+
+    pub fn scaling(self) -> DifficultyScaling {
+        match self {
+            Self::Story => DifficultyScaling {
+                enemy_health: 0.6,
+                enemy_damage: 0.5,
+                ai_speed: 0.8,
+                sky_lazer_density: 0.6,
+                player_immunity: 1.5,
+                attack_tokens: 1,
+                separation_radius: 1.3,
+                separation_speed: 1.2,
+            },
+            Self::Normal => DifficultyScaling {
+                enemy_health: 1.0,
+                enemy_damage: 1.0,
+                ai_speed: 1.0,
+                sky_lazer_density: 1.0,
+                player_immunity: 1.0,
+                attack_tokens: ATTACK_TOKENS,
+                separation_radius: 1.0,
+                separation_speed: 1.0,
+            },
+            Self::Hard => DifficultyScaling {
+                enemy_health: 1.3,
+                enemy_damage: 1.5,
+                ai_speed: 1.15,
+                sky_lazer_density: 1.4,
+                player_immunity: 0.8,
+                attack_tokens: ATTACK_TOKENS + 1,
+                separation_radius: 0.85,
+                separation_speed: 0.9,
+            },
+            Self::Nightmare => DifficultyScaling {
+                enemy_health: 1.7,
+                enemy_damage: 2.0,
+                ai_speed: 1.3,
+                sky_lazer_density: 1.8,
+                player_immunity: 0.6,
+                attack_tokens: ATTACK_TOKENS + 2,
+                separation_radius: 0.7,
+                separation_speed: 0.8,
+            },
+        }
+    }
+}
+
+fn scale_enemy_health(
+    difficulty: Res<Difficulty>,
+    mut enemies: Query<&mut Health, (Added<Health>, Without<Player>)>,
+) {
+    let scaling = difficulty.scaling().enemy_health;
+    for mut health in &mut enemies {
+        health.max *= scaling;
+        health.current *= scaling;
+    }
+}
+
+fn apply_crowd_settings(difficulty: Res<Difficulty>, mut crowd: ResMut<CrowdSettings>) {
+    let scaling = difficulty.scaling();
+    crowd.attack_tokens = scaling.attack_tokens;
+    crowd.separation_radius = ENEMY_SEPARATION_RADIUS * scaling.separation_radius;
+    crowd.separation_speed = ENEMY_SEPARATION_SPEED * scaling.separation_speed;
+}
@@ -0,0 +1,254 @@
+//! A small state machine for enemy AI.
+//!
+//! Each enemy type defines its states as an [`EnemyState`] enum and gets a [`StateMachine`]
+//! component for it. Transitions are applied in [`EnemyAiSystems::Tick`], after which the enemy's
+//! own systems in [`EnemyAiSystems::Act`] see [`StateMachine::exited`] and
+//! [`StateMachine::just_entered`] for one frame, so they can run exit, enter and update logic:
+//!
+//! ```ignore
+//! if let Some(previous) = machine.exited() { /* exit */ }
+//! if machine.just_entered() { /* enter */ }
+//! match machine.state() { /* update */ }
+//! ```
+//!
+//! In dev builds every state machine shows its current state above the enemy.
+
+use std::fmt::Debug;
+
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    difficulty::Difficulty,
+    enemy::{elite::Hasted, hit_reaction::HitFlash},
+    script::spawning::Materializing,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure_sets(
+        Update,
+        (EnemyAiSystems::Tick, EnemyAiSystems::Act)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+/// Tint pulsed on an enemy's [`Sprite`] while it is in a telegraph state.
+const TELEGRAPH_TINT: Color = Color::srgb(1.0, 0.45, 0.45);
+/// Pulses per second of the telegraph tint.
+const TELEGRAPH_PULSE_RATE: f32 = 8.0;
+
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum EnemyAiSystems {
+    /// Apply pending and timed transitions.
+    Tick,
+    /// Enemy decision making, run after the transitions of this frame.
+    Act,
+}
+
+pub trait EnemyState: Copy + Eq + Debug + Send + Sync + 'static {
+    /// Whether this state warns the player about what comes next.
+    /// Telegraph states pulse the enemy's [`Sprite`].
+    fn is_telegraph(&self) -> bool {
+        false
+    }
+}
+
+/// The colour an enemy's [`Sprite`] goes back to after a telegraph. White if missing.
+#[derive(Component, Clone, Copy)]
+pub struct SpriteTint(pub Color);
+
+#[derive(Component)]
+pub struct StateMachine<S: EnemyState> {
+    state: S,
+    /// Time spent in the current state.
+    elapsed: f32,
+    /// `elapsed` before the latest tick.
+    previous_elapsed: f32,
+    /// Move to the given state once `elapsed` reaches the given time.
+    timeout: Option<(f32, S)>,
+    /// Transition requested this frame, applied on the next tick.
+    pending: Option<S>,
+    entered: bool,
+    exited: Option<S>,
+}
+
+impl<S: EnemyState> StateMachine<S> {
+    /// Starts in `initial`. The initial state doesn't count as entered.
+    pub fn new(initial: S) -> Self {
+        Self {
+            state: initial,
+            elapsed: 0.0,
+            previous_elapsed: 0.0,
+            timeout: None,
+            pending: None,
+            entered: false,
+            exited: None,
+        }
+    }
+
+    pub fn with_timeout(mut self, seconds: f32, next: S) -> Self {
+        self.transition_after(seconds, next);
+        self
+    }
+
+    pub fn state(&self) -> S {
+        self.state
+    }
+
+    /// Seconds spent in the current state.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Seconds spent in the current state as of the previous tick. Negative on the frame the
+    /// state was entered, so anything scheduled at zero seconds counts as just reached.
+    ///
+    /// Use this instead of subtracting the frame time from [`StateMachine::elapsed`], since
+    /// machines tick at the difficulty's AI speed.
+    pub fn previous_elapsed(&self) -> f32 {
+        self.previous_elapsed
+    }
+
+    /// Seconds until the timed transition fires, if there is one.
+    pub fn remaining(&self) -> Option<f32> {
+        self.timeout.map(|(seconds, _)| seconds - self.elapsed)
+    }
+
+    /// True on the first frame in the current state.
+    pub fn just_entered(&self) -> bool {
+        self.entered
+    }
+
+    /// The state that was left this frame, if any.
+    pub fn exited(&self) -> Option<S> {
+        self.exited
+    }
+
+    /// Leaves the current state on the next tick. Overrides any timed transition.
+    pub fn transition(&mut self, next: S) {
+        self.pending = Some(next);
+    }
+
+    /// Leaves the current state `seconds` after it was entered.
+    pub fn transition_after(&mut self, seconds: f32, next: S) {
+        self.timeout = Some((seconds, next));
+    }
+
+    fn tick(&mut self, delta: f32) {
+        self.entered = false;
+        self.exited = None;
+        self.previous_elapsed = self.elapsed;
+        self.elapsed += delta;
+        let timed_out = self
+            .timeout
+            .filter(|(seconds, _)| self.elapsed >= *seconds)
+            .map(|(_, next)| next);
+        if let Some(next) = self.pending.take().or(timed_out) {
+            self.exited = Some(self.state);
+            self.state = next;
+            self.elapsed = 0.0;
+            self.previous_elapsed = f32::NEG_INFINITY;
+            self.timeout = None;
+            self.entered = true;
+        }
+    }
+}
+
+pub trait AddStateMachine {
+    /// Ticks every [`StateMachine<S>`] and shows its telegraphs.
+    fn add_state_machine<S: EnemyState>(&mut self) -> &mut Self;
+}
+
+impl AddStateMachine for App {
+    fn add_state_machine<S: EnemyState>(&mut self) -> &mut Self {
+        self.add_systems(
+            Update,
+            (
+                tick_state_machines::<S>.in_set(EnemyAiSystems::Tick),
+                pulse_telegraphs::<S>.after(EnemyAiSystems::Act),
+            ),
+        );
+        #[cfg(feature = "dev")]
+        self.add_systems(
+            Update,
+            (spawn_state_labels::<S>, update_state_labels::<S>)
+                .chain()
+                .after(EnemyAiSystems::Act),
+        );
+        self
+    }
+}
+
+fn tick_state_machines<S: EnemyState>(
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    // Enemies still fading in wait until they're fully there.
+    machines: Query<(&mut StateMachine<S>, Option<&Hasted>), Without<Materializing>>,
+) {
+    let ai_speed = difficulty.scaling().ai_speed;
+    for (mut machine, hasted) in machines {
+        // Hasted enemies get through their cooldowns and windups sooner.
+        machine.tick(time.delta_secs() * ai_speed * Hasted::factor(hasted));
+    }
+}
+
+fn pulse_telegraphs<S: EnemyState>(
+    // A hit flash takes over the colour until it's done.
+    mut machines: Query<(&StateMachine<S>, &mut Sprite, Option<&SpriteTint>), Without<HitFlash>>,
+) {
+    for (machine, mut sprite, tint) in &mut machines {
+        let base = tint.map_or(Color::WHITE, |tint| tint.0);
+        if machine.state().is_telegraph() {
+            let pulse = (machine.elapsed() * TELEGRAPH_PULSE_RATE * std::f32::consts::TAU).sin();
+            sprite.color = base.mix(&TELEGRAPH_TINT, 0.5 + 0.5 * pulse);
+        } else if machine.exited().is_some_and(|state| state.is_telegraph()) {
+            sprite.color = base;
+        }
+    }
+}
+
+#[cfg(feature = "dev")]
+const STATE_LABEL_HEIGHT: f32 = 200.0;
+
+/// Shows the state of its parent's [`StateMachine<S>`].
+#[cfg(feature = "dev")]
+#[derive(Component)]
+struct StateLabel;
+
+#[cfg(feature = "dev")]
+fn spawn_state_labels<S: EnemyState>(
+    mut commands: Commands,
+    machines: Query<Entity, Added<StateMachine<S>>>,
+) {
+    for entity in &machines {
+        commands.entity(entity).with_child((
+            Name::new("State Label"),
+            StateLabel,
+            Text2d::default(),
+            TextFont::from_font_size(40.0),
+            Transform::from_xyz(0.0, STATE_LABEL_HEIGHT, 10.0),
+        ));
+    }
+}
+
+#[cfg(feature = "dev")]
+fn update_state_labels<S: EnemyState>(
+    machines: Query<(&StateMachine<S>, &Children)>,
+    mut labels: Query<(&mut Text2d, &mut TextColor), With<StateLabel>>,
+) {
+    for (machine, children) in &machines {
+        let mut labels = labels.iter_many_mut(children);
+        while let Some((mut text, mut color)) = labels.fetch_next() {
+            text.0 = match machine.remaining() {
+                Some(remaining) => format!("{:?} {:.1}", machine.state(), remaining),
+                None => format!("{:?}", machine.state()),
+            };
+            color.0 = if machine.state().is_telegraph() {
+                TELEGRAPH_TINT
+            } else {
+                Color::WHITE
+            };
+        }
+    }
+}
@@ -1,18 +1,24 @@
 use crate::{
     PausableSystems,
     asset_tracking::LoadResource,
-    audio::sound_effect,
-    collision_layers::enemy_hurt_boxes,
-    enemy::eye::{EyeAssets, Pupil, RayWhite, the_eye},
-    health::hurtbox_prefab,
+    audio::{Music, sound_effect},
+    collision_layers::{GameLayer, enemy_hurt_boxes},
+    difficulty::Difficulty,
+    enemy::{
+        behaviour::{AddStateMachine, EnemyAiSystems, EnemyState, StateMachine},
+        boss_attacks::{AttackAction, BOSS_ATTACKS, BossAttack, EyeCue, LazerAim, LazerSpawn},
+        eye::{EyeAssets, Pupil, RayWhite, the_eye},
+    },
+    health::{Invulnerable, hurtbox_prefab},
     player::character::Player,
+    projectile::{Projectile, projectile, projectile_hitbox},
+    rng::GameRng,
+    screens::Screen,
+    script::spawning::Materializing,
+    tuning::{EnemyTuning, LazerAttackTuning, Tuning},
 };
 use avian2d::{math::*, prelude::*};
-use bevy::{
-    math::ops::{exp, sqrt},
-    prelude::*,
-    sprite::Anchor,
-};
+use bevy::{audio::Volume, math::ops::sqrt, prelude::*, sprite::Anchor};
 use rand::{Rng, seq::SliceRandom};
 use statrs::distribution::{ContinuousCDF, Normal};
 
@@ -22,9 +28,26 @@ use crate::health::Health;
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<LazerAssets>();
     app.load_resource::<LazerAssets>();
+    app.register_type::<BossMusicAssets>();
+    app.load_resource::<BossMusicAssets>();
+    app.add_state_machine::<BossState>();
     app.add_systems(
         Update,
-        (enemy_decision_making, tick_lazers).in_set(PausableSystems),
+        (
+            advance_boss_phase.in_set(EnemyAiSystems::Tick),
+            (enemy_decision_making, break_chains)
+                .chain()
+                .in_set(EnemyAiSystems::Act),
+            (
+                fire_lazers,
+                tick_lazers,
+                sweep_lazers,
+                (spawn_landing_markers, despawn_landing_markers),
+                tick_chain_shards,
+                fade_music,
+            )
+                .in_set(PausableSystems),
+        ),
     );
 }
 
@@ -35,13 +58,15 @@ pub fn boss(
     eye_assets: &EyeAssets,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
     translation: Vec3,
+    tuning: &EnemyTuning,
 ) -> impl Bundle {
     let scale = Vec2::splat(1.0);
     (
         Name::new("Boss"),
         the_eye(&eye_assets, texture_atlas_layouts, scale, translation),
         BossController::new(),
-        Health::new(BOSS_HEALTH),
+        StateMachine::new(BossState::Idle),
+        Health::new(tuning.boss_health),
         Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
         Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
         ColliderDensity(2.0),
@@ -75,75 +100,226 @@ impl FromWorld for LazerAssets {
         }
     }
 }
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct BossMusicAssets {
+    /// One per boss phase, see [`BossPhaseConfig::music`].
+    #[dependency]
+    phases: Vec<Handle<AudioSource>>,
+}
+
+impl FromWorld for BossMusicAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            phases: BOSS_PHASES
+                .iter()
+                .map(|phase| assets.load(phase.music))
+                .collect(),
+        }
+    }
+}
+
+/// A lazer that shows as a thin aiming line for `windup` seconds, then fires for `time_remaining`.
+/// Its hurtbox only turns on once it fires.
 pub fn lazer(
     lazer_assets: &LazerAssets,
     translation: Vec3,
     direction: Vec3,
     time_remaining: f32,
+    windup: f32,
     scale: Vec3,
-    // this is just saying do we want sky or no sky
+    // Beams from the eye glint where they come out and get the long sound, sky lazers don't.
     with_glint: bool,
 ) -> impl Bundle {
-    let size = Vec2::new(6035.0 / 10., 477.0 / 10.);
-    let visibility = if with_glint {
-        Visibility::Inherited
-    } else {
-        Visibility::Hidden
-    };
-    let sound_effect = if with_glint {
-        sound_effect(lazer_assets.laser_long.clone())
-    } else {
-        let rng = &mut rand::thread_rng();
-        let sound = lazer_assets.laser_short.choose(rng).unwrap().clone();
-        sound_effect(sound)
-    };
-
-    let glint = (
-        Name::new("Glint"),
-        Sprite::from_image(lazer_assets.glint.clone()),
-        Transform::default()
-            .with_translation(-translation)
-            .with_rotation(Quat::from_rotation_z(direction.angle_between(-Vec3::AXES[0])).inverse())
-            .with_scale(-scale),
-        visibility,
-    );
+    let (collider, collider_transform) = lazer_shape(scale);
     (
         Name::new("Lazer"),
         Sprite {
             image: lazer_assets.img.clone(),
-            custom_size: Some(size),
-            anchor: Anchor::Custom(Vec2::new(5820. / 6035. - 0.5, 0.0)),
+            custom_size: Some(LAZER_SIZE),
+            anchor: Anchor::Custom(Vec2::new(LAZER_ANCHOR_X, 0.0)),
+            color: Color::WHITE.with_alpha(AIMING_LINE_ALPHA),
             ..default()
         },
         Transform::default()
             .with_translation(translation)
-            .with_rotation(Quat::from_rotation_z(
-                direction.angle_between(-Vec3::AXES[0]),
-            ))
-            .with_scale(scale),
-        children![
-            hurtbox_prefab(
-                Collider::rectangle(0.95 * size.x * scale.x, 0.2 * size.y),
-                enemy_hurt_boxes(),
-                0.05,
-                Transform::from_translation(Vec3::new(
-                    -(5820. / 6035. - 0.5) * 0.95 * size.x,
-                    0.,
-                    0.
-                )),
-            ),
-            sound_effect,
-        ],
+            .with_rotation(lazer_rotation(direction))
+            .with_scale(scale.with_y(scale.y * AIMING_LINE_THICKNESS)),
+        children![(
+            hurtbox_prefab(collider, enemy_hurt_boxes(), 0.05, collider_transform),
+            ColliderDisabled,
+        )],
         Lazer { time_remaining },
+        LazerWindup {
+            time_remaining: windup,
+            scale,
+            with_glint,
+        },
+    )
+}
+
+const LAZER_SIZE: Vec2 = Vec2::new(6035.0 / 10., 477.0 / 10.);
+/// Where the lazer comes out of, on its sprite.
+const LAZER_ANCHOR_X: f32 = 5820. / 6035. - 0.5;
+
+/// The part of a lazer that can hit and be hit, in its local space.
+fn lazer_shape(scale: Vec3) -> (Collider, Transform) {
+    (
+        Collider::rectangle(0.95 * LAZER_SIZE.x * scale.x, 0.2 * LAZER_SIZE.y),
+        Transform::from_xyz(-LAZER_ANCHOR_X * 0.95 * LAZER_SIZE.x, 0., 0.),
     )
 }
 
+/// How much of the lazer's width the aiming line keeps.
+const AIMING_LINE_THICKNESS: f32 = 0.12;
+const AIMING_LINE_ALPHA: f32 = 0.45;
+/// Gravity for sky lazers once they fire, relative to everything else.
+/// They hang still while winding up.
+const SKY_LAZER_GRAVITY_SCALE: f32 = 0.8;
+
+/// A lazer that hasn't fired yet.
+#[derive(Component)]
+pub struct LazerWindup {
+    time_remaining: f32,
+    /// The scale to fire at.
+    scale: Vec3,
+    with_glint: bool,
+}
+
+fn fire_lazers(
+    mut commands: Commands,
+    time: Res<Time>,
+    lazer_assets: Res<LazerAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<Tuning>,
+    mut lazers: Query<(
+        Entity,
+        &mut LazerWindup,
+        &mut Sprite,
+        &mut Transform,
+        &Children,
+        Option<&mut Projectile>,
+    )>,
+) {
+    for (entity, mut windup, mut sprite, mut transform, children, projectile) in &mut lazers {
+        windup.time_remaining -= time.delta_secs();
+        if windup.time_remaining > 0.0 {
+            continue;
+        }
+        sprite.color = Color::WHITE;
+        transform.scale = windup.scale;
+        if let Some(mut projectile) = projectile {
+            projectile.gravity = SKY_LAZER_GRAVITY_SCALE * tuning.physics.gravity;
+        }
+        for child in children {
+            commands.entity(*child).remove::<ColliderDisabled>();
+        }
+        if windup.with_glint {
+            // Undo the lazer's stretch, so the glint keeps its own proportions.
+            commands.entity(entity).with_child((
+                Name::new("Glint"),
+                Sprite::from_image(lazer_assets.glint.clone()),
+                Transform::from_scale(windup.scale.recip()).with_translation(Vec3::Z * 0.1),
+            ));
+        }
+        let sound = if windup.with_glint {
+            lazer_assets.laser_long.clone()
+        } else {
+            lazer_assets
+                .laser_short
+                .choose(&mut rng.audio)
+                .unwrap()
+                .clone()
+        };
+        commands
+            .entity(entity)
+            .remove::<LazerWindup>()
+            .with_child(sound_effect(sound));
+    }
+}
+
+/// Marks the ground under a sky lazer until it lands.
+#[derive(Component)]
+struct LandingMarker {
+    lazer: Entity,
+}
+
+const LANDING_MARKER_COLOR: Color = Color::srgba(1.0, 0.2, 0.2, 0.7);
+const LANDING_MARKER_SIZE: Vec2 = Vec2::new(70.0, 10.0);
+
+fn spawn_landing_markers(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    lazers: Query<(Entity, &Transform), (Added<Lazer>, With<Projectile>)>,
+) {
+    for (lazer, transform) in &lazers {
+        let origin = transform.translation.truncate();
+        let Some(hit) = spatial_query.cast_ray(
+            origin,
+            Dir2::NEG_Y,
+            Scalar::MAX,
+            true,
+            &SpatialQueryFilter::from_mask(GameLayer::Ground),
+        ) else {
+            continue;
+        };
+        let ground = origin - Vec2::Y * hit.distance;
+        commands.spawn((
+            Name::new("Landing Marker"),
+            LandingMarker { lazer },
+            Sprite::from_color(LANDING_MARKER_COLOR, LANDING_MARKER_SIZE),
+            Transform::from_translation(ground.extend(6.0)),
+            StateScoped(Screen::Gameplay),
+        ));
+    }
+}
+
+fn despawn_landing_markers(
+    mut commands: Commands,
+    markers: Query<(Entity, &LandingMarker, &Transform)>,
+    lazers: Query<&Transform, With<Lazer>>,
+) {
+    for (entity, marker, transform) in &markers {
+        let landed = lazers
+            .get(marker.lazer)
+            .map_or(true, |lazer| lazer.translation.y <= transform.translation.y);
+        if landed {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// The beam sprite points along -X.
+fn lazer_rotation(direction: Vec3) -> Quat {
+    Quat::from_rotation_z(Vec2::NEG_X.angle_to(direction.truncate()))
+}
+
+/// Turns a lazer around its origin.
+#[derive(Component, Clone, Copy)]
+struct Sweep {
+    angular_velocity: f32,
+}
+
+fn sweep_lazers(
+    time: Res<Time>,
+    mut lazers: Query<(&Sweep, &mut Transform), Without<LazerWindup>>,
+) {
+    for (sweep, mut transform) in &mut lazers {
+        transform.rotate_z(sweep.angular_velocity * time.delta_secs());
+    }
+}
+
 #[derive(Component)]
 pub struct Lazer {
     time_remaining: f32,
 }
 
-fn tick_lazers(mut commands: Commands, time: Res<Time>, lazers: Query<(Entity, &mut Lazer)>) {
+fn tick_lazers(
+    mut commands: Commands,
+    time: Res<Time>,
+    lazers: Query<(Entity, &mut Lazer), Without<LazerWindup>>,
+) {
     for (entity, mut lazer) in lazers {
         lazer.time_remaining -= time.delta_secs().adjust_precision();
         if lazer.time_remaining < 0.0 {
@@ -152,26 +328,114 @@ fn tick_lazers(mut commands: Commands, time: Res<Time>, lazers: Query<(Entity, &
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BossState {
+    /// Picking the next attack.
+    Idle,
+    /// Cooling down after an attack.
+    Recover,
+    /// Warning the player about an attack, holds an index into [`BOSS_ATTACKS`].
+    Telegraph(usize),
+    /// Carrying out an attack, holds an index into [`BOSS_ATTACKS`].
+    Attack(usize),
+    /// Breaking its chains on the way into the next phase.
+    Unchaining,
+}
+
+impl BossState {
+    /// The attack being telegraphed or carried out.
+    pub fn attack(&self) -> Option<&'static BossAttack> {
+        match self {
+            Self::Telegraph(attack) | Self::Attack(attack) => BOSS_ATTACKS.get(*attack),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for BossState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = |attack: &usize| BOSS_ATTACKS.get(*attack).map_or("?", |attack| attack.name);
+        match self {
+            Self::Idle => write!(f, "Idle"),
+            Self::Recover => write!(f, "Recover"),
+            Self::Telegraph(attack) => write!(f, "Telegraph({})", name(attack)),
+            Self::Attack(attack) => write!(f, "Attack({})", name(attack)),
+            Self::Unchaining => write!(f, "Unchaining"),
+        }
+    }
+}
+
+impl EnemyState for BossState {
+    fn is_telegraph(&self) -> bool {
+        matches!(self, Self::Telegraph(_))
+    }
+}
+
 #[derive(Component)]
 pub struct BossController {
+    /// Index into [`BOSS_PHASES`].
+    phase: usize,
+    /// Whether the eye has broken free of its chains, i.e. is past its first phase.
     unchained: bool,
-    time_until_next_attack: f32,
-    time_since_last_reposition_ended: f32,
-    pub sky_lazer_remaining_duration: f32,
-    pub beam_lazer_remaining_duration: f32,
+    /// Seconds since each of the [`BOSS_ATTACKS`] last ended.
+    time_since_attacks: Vec<f32>,
+    /// Whether the current attack is flipped, see [`BossAttack::mirror_randomly`].
+    mirrored: bool,
     repositioning_to_left: bool,
 }
 impl BossController {
     fn new() -> Self {
         Self {
+            phase: 0,
             unchained: false,
-            time_until_next_attack: 0.0,
-            time_since_last_reposition_ended: 0.0,
-            sky_lazer_remaining_duration: 0.0,
-            beam_lazer_remaining_duration: 0.0,
+            time_since_attacks: vec![0.0; BOSS_ATTACKS.len()],
+            mirrored: false,
             repositioning_to_left: true,
         }
     }
+
+    pub fn unchained(&self) -> bool {
+        self.unchained
+    }
+
+    /// Counting from 1, like the phase log messages.
+    pub fn phase_number(&self) -> usize {
+        self.phase + 1
+    }
+
+    fn phase(&self) -> &'static BossPhaseConfig {
+        &BOSS_PHASES[self.phase]
+    }
+
+    /// Whether the conditions of the attack at `index` hold with the player `distance` away.
+    fn can_use(&self, index: usize, distance: f32) -> bool {
+        let conditions = &BOSS_ATTACKS[index].conditions;
+        distance <= conditions.max_range
+            && self.time_since_attacks[index] >= conditions.cooldown
+            && conditions.phases.contains(&self.phase)
+    }
+}
+
+/// Moves the boss into its next phase once its health drops low enough.
+fn advance_boss_phase(boss: Single<(&mut BossController, &mut StateMachine<BossState>, &Health)>) {
+    let (mut boss, mut machine, health) = boss.into_inner();
+    // Finish flying over first, otherwise the next reposition would start from mid-air.
+    let repositioning = machine
+        .state()
+        .attack()
+        .is_some_and(|attack| matches!(attack.action, AttackAction::Reposition));
+    if repositioning || machine.state() == BossState::Unchaining {
+        return;
+    }
+    let Some(next) = BOSS_PHASES.get(boss.phase + 1) else {
+        return;
+    };
+    if health.current <= next.health_threshold * health.max {
+        boss.phase += 1;
+        boss.unchained = true;
+        info!("Boss entering phase {}", boss.phase + 1);
+        machine.transition(BossState::Unchaining);
+    }
 }
 
 fn enemy_decision_making(
@@ -180,125 +444,369 @@ fn enemy_decision_making(
     target: Single<&Transform, With<Player>>,
     pupil: Single<&GlobalTransform, (With<Pupil>, Without<Player>, Without<RayWhite>)>,
     mut eye_white: Single<&mut Sprite, (With<RayWhite>, Without<Player>, Without<Pupil>)>,
-    boss: Single<(Entity, &mut BossController, &mut Transform), (Without<Pupil>, Without<Player>)>,
+    boss: Single<
+        (
+            &mut BossController,
+            &mut StateMachine<BossState>,
+            &mut Transform,
+        ),
+        (Without<Pupil>, Without<Player>, Without<Materializing>),
+    >,
     lazer_assets: Res<LazerAssets>,
     eye_assets: Res<EyeAssets>,
+    mut rng: ResMut<GameRng>,
+    difficulty: Res<Difficulty>,
+    tuning: Res<Tuning>,
 ) {
-    let (entity, mut boss, mut pos) = boss.into_inner();
+    let (mut boss, mut machine, mut pos) = boss.into_inner();
     let delta_time = time.delta_secs_f64().adjust_precision();
-    //reapersitioning
-    boss.time_until_next_attack -= delta_time;
-    boss.time_since_last_reposition_ended += delta_time;
-    boss.sky_lazer_remaining_duration -= delta_time;
-    boss.beam_lazer_remaining_duration -= delta_time;
-    eye_white.image = eye_assets.white.clone();
-
-    if boss.time_since_last_reposition_ended - delta_time < 0. {
-        let current_t: f32 = if boss.repositioning_to_left {
-            TIME_TO_REPOSITION + boss.time_since_last_reposition_ended.min(0.)
-        } else {
-            -boss.time_since_last_reposition_ended.min(0.)
-        };
-        let a: f32 = sqrt(2.) * (POSITION_2_X - POSITION_1.x) / 2.;
-        let b: f32 = (MAX_REPOSITIONING_Y - POSITION_1.y) / (1. + 1. / sqrt(2.0));
-        let x_trans: f32 = (POSITION_2_X + POSITION_1.x) / 2.;
-        let y_trans: f32 = MAX_REPOSITIONING_Y - b;
-        let lerp = std::f32::consts::PI / 4.
-            * (-1. * (1. - current_t / TIME_TO_REPOSITION) + 5. * current_t / TIME_TO_REPOSITION);
-        pos.translation.x = a * lerp.cos() + x_trans;
-        pos.translation.y = b * lerp.sin() + y_trans;
-        return;
+    let boss_tuning = &tuning.enemy.boss;
+    let phase = boss_tuning.phase(boss.phase);
+
+    for time_since_attack in &mut boss.time_since_attacks {
+        *time_since_attack += delta_time;
+    }
+    if let Some(BossState::Attack(attack)) = machine.exited() {
+        boss.time_since_attacks[attack] = 0.0;
+    }
+    if machine.just_entered() {
+        match machine.state() {
+            BossState::Recover => {
+                machine.transition_after(phase.time_between_attacks, BossState::Idle);
+            }
+            BossState::Telegraph(attack) => {
+                boss.mirrored = BOSS_ATTACKS[attack].mirror_randomly && rng.ai.gen_bool(0.5);
+                machine.transition_after(
+                    BOSS_ATTACKS[attack].telegraph(boss_tuning),
+                    BossState::Attack(attack),
+                );
+            }
+            BossState::Attack(attack) => match BOSS_ATTACKS[attack].action {
+                AttackAction::Reposition => {
+                    boss.repositioning_to_left = !boss.repositioning_to_left;
+                    machine.transition_after(phase.time_to_reposition, BossState::Recover);
+                }
+                AttackAction::Lazers { timing, .. } => {
+                    machine.transition_after(timing.of(boss_tuning).duration, BossState::Recover);
+                }
+            },
+            BossState::Unchaining => {
+                machine.transition_after(BOSS_UNCHAIN_DURATION, BossState::Recover);
+            }
+            BossState::Idle => {}
+        }
     }
 
-    //sky beamin
-    if boss.sky_lazer_remaining_duration > 0. {
-        eye_white.image = eye_assets.red.clone();
-
-        //start lazer
-        if boss.sky_lazer_remaining_duration + delta_time < SKY_ATTACK_START_TIME
-            && boss.sky_lazer_remaining_duration >= SKY_ATTACK_START_TIME
-        {
-            commands.spawn(lazer(
-                &lazer_assets,
-                pupil.translation().with_z(7.),
-                Vec3::new(0.0, 1.0, 0.0),
-                boss.sky_lazer_remaining_duration,
-                RAINING_LASER_SCALE,
-                false,
-            ));
+    let looking_up = machine
+        .state()
+        .attack()
+        .is_some_and(|attack| attack.eye == EyeCue::LookUp);
+    eye_white.image = if looking_up || machine.state() == BossState::Unchaining {
+        eye_assets.red.clone()
+    } else {
+        eye_assets.white.clone()
+    };
+
+    match machine.state() {
+        BossState::Attack(attack) => match BOSS_ATTACKS[attack].action {
+            AttackAction::Reposition => {
+                let time_to_reposition = phase.time_to_reposition;
+                let progress = machine.elapsed().min(time_to_reposition);
+                let current_t: f32 = if boss.repositioning_to_left {
+                    progress
+                } else {
+                    time_to_reposition - progress
+                };
+                let a: f32 = sqrt(2.) * (POSITION_2_X - POSITION_1.x) / 2.;
+                let b: f32 = (MAX_REPOSITIONING_Y - POSITION_1.y) / (1. + 1. / sqrt(2.0));
+                let x_trans: f32 = (POSITION_2_X + POSITION_1.x) / 2.;
+                let y_trans: f32 = MAX_REPOSITIONING_Y - b;
+                let lerp = std::f32::consts::PI / 4.
+                    * (-1. * (1. - current_t / time_to_reposition)
+                        + 5. * current_t / time_to_reposition);
+                pos.translation.x = a * lerp.cos() + x_trans;
+                pos.translation.y = b * lerp.sin() + y_trans;
+            }
+            //spawn lazers
+            AttackAction::Lazers { timing, spawns } => {
+                let timing = timing.of(boss_tuning);
+                let previous = machine.previous_elapsed();
+                let now = machine.elapsed();
+                let side = if boss.mirrored { -1.0 } else { 1.0 };
+                let density = difficulty.scaling().sky_lazer_density;
+                for spawn in spawns
+                    .iter()
+                    .filter(|spawn| spawn.fires(timing.spawn_every, previous, now, density))
+                {
+                    spawn_lazers(
+                        &mut commands,
+                        &lazer_assets,
+                        &mut rng,
+                        spawn,
+                        timing,
+                        boss_tuning.sky_lazer_damage,
+                        side,
+                        pupil.translation(),
+                        target.translation,
+                    );
+                }
+            }
+        },
+        //attacks
+        BossState::Idle => {
+            let distance = target.translation.distance(pos.translation);
+            let usable: Vec<_> = (0..BOSS_ATTACKS.len())
+                .filter(|attack| boss.can_use(*attack, distance))
+                .collect();
+            let urgent = usable.iter().copied().find(|attack| {
+                BOSS_ATTACKS[*attack]
+                    .urgency
+                    .as_ref()
+                    .is_some_and(|urgency| {
+                        let roll = rng.ai.gen_range(0.0..1.0);
+                        urgency.rolls(roll, boss.time_since_attacks[*attack], delta_time)
+                    })
+            });
+            let choice = urgent.or_else(|| {
+                usable
+                    .choose_weighted(&mut rng.ai, |attack| BOSS_ATTACKS[*attack].weight)
+                    .ok()
+                    .copied()
+            });
+            if let Some(attack) = choice {
+                machine.transition(BossState::Telegraph(attack));
+            }
         }
-        //spawn lazers
-        if (boss.sky_lazer_remaining_duration + delta_time) % SKY_LAZER_SPAWN_FREQUENCY
-            < boss.sky_lazer_remaining_duration % SKY_LAZER_SPAWN_FREQUENCY
-        {
-            if let Ok(dist) = Normal::new(target.translation.x.into(), 200.0) {
-                let roll: f64 = rand::thread_rng().gen_range(0.0..1.0);
+        BossState::Recover | BossState::Telegraph(_) | BossState::Unchaining => {}
+    }
+}
 
+/// Spawns the lazers of one [`LazerSpawn`]. `side` is -1 for a mirrored attack.
+fn spawn_lazers(
+    commands: &mut Commands,
+    lazer_assets: &LazerAssets,
+    rng: &mut GameRng,
+    spawn: &LazerSpawn,
+    timing: &LazerAttackTuning,
+    sky_lazer_damage: f32,
+    side: f32,
+    pupil: Vec3,
+    target: Vec3,
+) {
+    let sweep = Sweep {
+        angular_velocity: side * spawn.sweep / timing.lazer_duration,
+    };
+    match spawn.aim {
+        LazerAim::AtPlayer { offset } => {
+            let aim = Vec2::from_angle(side * offset).rotate((target - pupil).truncate());
+            commands.spawn((
+                lazer(
+                    lazer_assets,
+                    pupil.with_z(7.),
+                    aim.extend(0.0),
+                    timing.lazer_duration,
+                    timing.windup,
+                    BEAM_LASER_SCALE,
+                    true,
+                ),
+                sweep,
+            ));
+        }
+        LazerAim::Ring { count } => {
+            let offset = rng.ai.gen_range(0.0..std::f32::consts::TAU);
+            for i in 0..count {
+                let angle = offset + std::f32::consts::TAU * i as f32 / count as f32;
                 commands.spawn((
                     lazer(
-                        &lazer_assets,
-                        Vec3::new(dist.inverse_cdf(roll) as f32, 1200., 7.),
-                        Vec3::new(0.0, -1.0, 0.0),
-                        SKY_LAZER_DURATION,
-                        RAINING_LASER_SCALE,
-                        false,
+                        lazer_assets,
+                        pupil.with_z(7.),
+                        Vec2::from_angle(angle).extend(0.0),
+                        timing.lazer_duration,
+                        timing.windup,
+                        BEAM_LASER_SCALE,
+                        i == 0,
                     ),
-                    Collider::capsule(1., 1.),
-                    CollisionLayers::new(0b00010, 0b00000),
-                    RigidBody::Dynamic,
-                    GravityScale(0.8),
+                    sweep,
                 ));
             }
-            return;
+        }
+        LazerAim::RainAroundPlayer { sigma } => {
+            if let Ok(dist) = Normal::new(target.x.into(), sigma.into()) {
+                let roll: f64 = rng.ai.gen_range(0.0..1.0);
+
+                let (collider, collider_transform) = lazer_shape(RAINING_LASER_SCALE);
+                commands
+                    .spawn((
+                        lazer(
+                            lazer_assets,
+                            Vec3::new(dist.inverse_cdf(roll) as f32, 1200., 7.),
+                            Vec3::new(0.0, -1.0, 0.0),
+                            timing.lazer_duration,
+                            timing.windup,
+                            RAINING_LASER_SCALE,
+                            false,
+                        ),
+                        // Starts falling once it fires.
+                        projectile(
+                            Projectile::new(timing.windup + timing.lazer_duration).piercing(),
+                            Vec2::ZERO,
+                        ),
+                    ))
+                    .with_child((
+                        projectile_hitbox(collider, sky_lazer_damage, collider_transform),
+                        ColliderDisabled,
+                    ));
+            }
         }
     }
+}
 
-    //attacks
-    let roll: f32 = rand::thread_rng().gen_range(0.0..1.0);
-    let target_coords = target.translation;
-    let relative_coords = target_coords - pos.translation;
-    //good time for a reposition attack?
-    if boss.time_until_next_attack <= 0.0
-        && relative_coords.length_squared() <= 600_f32.powf(2.)
-        && roll.powf(1.0 / delta_time)
-            > 1. - 1.0 / (1. + exp(-0.7 * (boss.time_since_last_reposition_ended - 15.0)))
-    {
-        // dbg!(roll);
-        // dbg!(boss.time_since_last_reposition_ended);
-        // dbg!(roll.powf(1.5 / delta_time));
-        // dbg!(1. - 1.0 / (1. + exp(-0.7 * (boss.time_since_last_reposition_ended - 15.0))));
-        boss.time_until_next_attack = BOSS_TIME_BETWEEN_ATTACKS + TIME_TO_REPOSITION;
-        boss.time_since_last_reposition_ended = -TIME_TO_REPOSITION;
-        boss.repositioning_to_left = !boss.repositioning_to_left;
+/// A bit of chain flying off the eye as it breaks free.
+#[derive(Component)]
+struct ChainShard {
+    velocity: Vec2,
+    spin: f32,
+    time_remaining: f32,
+}
+
+const CHAIN_SHARD_COLOR: Color = Color::srgb(0.55, 0.55, 0.6);
+const CHAIN_SHARD_SPEED: f32 = 700.0;
+const CHAIN_SHARD_GRAVITY: f32 = 1400.0;
+
+/// Plays the chain breaking when the boss enters [`BossState::Unchaining`] and keeps it
+/// invulnerable until it's done. The music crossfades into the next phase's.
+fn break_chains(
+    mut commands: Commands,
+    boss: Single<(
+        Entity,
+        &BossController,
+        &StateMachine<BossState>,
+        &Transform,
+    )>,
+    music: Query<Entity, With<Music>>,
+    music_assets: Res<BossMusicAssets>,
+    lazer_assets: Res<LazerAssets>,
+    mut rng: ResMut<GameRng>,
+) {
+    let (entity, boss, machine, transform) = boss.into_inner();
+    if machine.exited() == Some(BossState::Unchaining) {
+        commands.entity(entity).remove::<Invulnerable>();
+    }
+    if !(machine.just_entered() && machine.state() == BossState::Unchaining) {
         return;
     }
 
-    //good time for a beam attack?
-    if boss.time_until_next_attack <= 0.0 && relative_coords.length_squared() <= 500_f32.powf(2.) {
-        boss.time_until_next_attack = BOSS_TIME_BETWEEN_ATTACKS + BEAM_ATTACK_DURATION;
-        boss.beam_lazer_remaining_duration = BEAM_ATTACK_DURATION;
-        commands.spawn(lazer(
-            &lazer_assets,
-            pupil.translation().with_z(7.),
-            target.translation - pupil.translation(),
-            BEAM_LAZER_DURATION,
-            BEAM_LASER_SCALE,
-            true,
+    commands.entity(entity).insert(Invulnerable);
+    commands.spawn(sound_effect(lazer_assets.laser_long.clone()));
+    for i in 0..CHAIN_SHARD_COUNT {
+        let angle = std::f32::consts::TAU * i as f32 / CHAIN_SHARD_COUNT as f32
+            + rng.audio.gen_range(-0.2..0.2);
+        let direction = Vec2::from_angle(angle);
+        commands.spawn((
+            Name::new("Chain Shard"),
+            Sprite::from_color(CHAIN_SHARD_COLOR, Vec2::new(36.0, 14.0)),
+            Transform::from_translation(
+                (transform.translation.truncate() + direction * 150.0).extend(8.0),
+            )
+            .with_rotation(Quat::from_rotation_z(angle)),
+            ChainShard {
+                velocity: direction * CHAIN_SHARD_SPEED * rng.audio.gen_range(0.6..1.2),
+                spin: rng.audio.gen_range(-12.0..12.0),
+                time_remaining: BOSS_UNCHAIN_DURATION,
+            },
+            StateScoped(Screen::Gameplay),
         ));
-        // commands.spawn(lazer());
-        return;
     }
-    //always a good time for a sky beam attack
-    if boss.time_until_next_attack <= 0.0 {
-        boss.sky_lazer_remaining_duration = SKY_ATTACK_DURATION;
-        boss.time_until_next_attack = BOSS_TIME_BETWEEN_ATTACKS + SKY_ATTACK_DURATION;
-        return;
+
+    for music in &music {
+        commands.entity(music).insert(MusicFade::fading_out());
     }
+    commands.spawn((
+        Name::new("Music"),
+        StateScoped(Screen::Gameplay),
+        AudioPlayer(music_assets.phases[boss.phase].clone()),
+        // Silent until the fade brings it in.
+        PlaybackSettings::LOOP
+            .with_speed(boss.phase().music_speed)
+            .with_volume(Volume::SILENT),
+        Music,
+        MusicFade::fading_in(),
+    ));
+}
+
+/// Fades music in or out over [`BOSS_MUSIC_CROSSFADE`] seconds. Music that fades out is
+/// despawned after.
+#[derive(Component)]
+struct MusicFade {
+    elapsed: f32,
+    fading_in: bool,
+}
 
-    // pub const SKY_LAZER_DURATION: f32 = 1.;
-    // pub const SKY_LAZER_SPAWN_FREQUENCY: f32 = 0.5;
-    // pub const SKY_LAZER_ATTACK_DURATION: f32 = 5.0;
-    // pub const BEAM_LAZER_DURATION: f32 = 1.65;
-    // pub const BEAM_LAZER_WIDTH: f32 = 2.;
-    // pub const BEAM_ATTACK_DURATION: f32 = BEAM_LAZER_DURATION + 0.35;
+impl MusicFade {
+    fn fading_in() -> Self {
+        Self {
+            elapsed: 0.0,
+            fading_in: true,
+        }
+    }
+
+    fn fading_out() -> Self {
+        Self {
+            elapsed: 0.0,
+            fading_in: false,
+        }
+    }
+}
+
+fn fade_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    global_volume: Res<GlobalVolume>,
+    mut fades: Query<(
+        Entity,
+        &mut MusicFade,
+        &mut PlaybackSettings,
+        Option<&mut AudioSink>,
+    )>,
+) {
+    for (entity, mut fade, mut playback, sink) in &mut fades {
+        fade.elapsed += time.delta_secs();
+        let progress = (fade.elapsed / BOSS_MUSIC_CROSSFADE).min(1.0);
+        let volume = if fade.fading_in {
+            Volume::Linear(progress)
+        } else {
+            playback.volume * Volume::Linear(1.0 - progress)
+        };
+        if let Some(mut sink) = sink {
+            sink.set_volume(global_volume.volume * volume);
+        }
+        if progress < 1.0 {
+            continue;
+        }
+        if fade.fading_in {
+            // So changes to the global volume keep applying to it.
+            playback.volume = Volume::Linear(1.0);
+            commands.entity(entity).remove::<MusicFade>();
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn tick_chain_shards(
+    mut commands: Commands,
+    time: Res<Time>,
+    shards: Query<(Entity, &mut ChainShard, &mut Transform)>,
+) {
+    let delta_time = time.delta_secs();
+    for (entity, mut shard, mut transform) in shards {
+        shard.time_remaining -= delta_time;
+        if shard.time_remaining < 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        shard.velocity.y -= CHAIN_SHARD_GRAVITY * delta_time;
+        transform.translation += (shard.velocity * delta_time).extend(0.0);
+        transform.rotate_z(shard.spin * delta_time);
+    }
 }
@@ -0,0 +1,262 @@
+//! The boss's attacks, described as data.
+//!
+//! Every time the boss is ready to act it picks one of the [`BOSS_ATTACKS`] whose
+//! [`AttackConditions`] hold, weighted by [`BossAttack::weight`]. To give an attack a different
+//! weight in each phase, list it once per phase. Attacks with an [`Urgency`] are rolled for
+//! before that pick instead. How long the lazer attacks take is tuned in [`BossTuning`].
+
+use std::ops::RangeInclusive;
+
+use crate::{
+    enemy::configs::*,
+    tuning::{BossTuning, LazerAttackTuning},
+};
+
+pub struct BossAttack {
+    /// Shown in logs and the dev state labels.
+    pub name: &'static str,
+    pub conditions: AttackConditions,
+    pub weight: f32,
+    pub urgency: Option<Urgency>,
+    pub action: AttackAction,
+    pub eye: EyeCue,
+    /// Flip every offset and spin of the attack at random, so it comes from either side.
+    pub mirror_randomly: bool,
+}
+
+pub struct AttackConditions {
+    /// Only when the player is closer than this.
+    pub max_range: f32,
+    /// Seconds that have to pass since this attack last ended.
+    pub cooldown: f32,
+    /// Indices into [`BOSS_PHASES`] this attack is used in.
+    pub phases: RangeInclusive<usize>,
+}
+
+/// Rolled for every frame the boss is idle, before the weighted pick. The odds climb along a
+/// logistic curve of the seconds since the attack last ended.
+pub struct Urgency {
+    /// Seconds at which the curve is halfway up.
+    pub midpoint: f32,
+    pub steepness: f32,
+}
+
+pub enum AttackAction {
+    /// Fly over to the other side of the arena.
+    Reposition,
+    /// Spawn lazers on a schedule, timed by `timing`.
+    Lazers {
+        timing: LazerTiming,
+        spawns: &'static [LazerSpawn],
+    },
+}
+
+/// Which of the [`BossTuning`] lazer timings an attack uses.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LazerTiming {
+    Beam,
+    Ring,
+    SkyRain,
+}
+
+/// What the eye does during the telegraph and the attack.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EyeCue {
+    /// Keep looking at the player.
+    Track,
+    /// Stop moving the pupil.
+    Hold,
+    /// Turn red and look up at the sky.
+    LookUp,
+}
+
+pub struct LazerSpawn {
+    /// Seconds into the attack, after the telegraph.
+    pub at: f32,
+    pub aim: LazerAim,
+    /// Radians the lazer turns around its origin while it hurts.
+    pub sweep: f32,
+}
+
+pub enum LazerAim {
+    /// A beam from the pupil at the player, turned by `offset` radians.
+    AtPlayer { offset: f32 },
+    /// `count` evenly spaced beams from the pupil, starting at a random angle.
+    Ring { count: usize },
+    /// Falls from the sky, normally distributed around the player with `sigma`.
+    RainAroundPlayer { sigma: f32 },
+}
+
+const BEAM_RANGE: f32 = 500.0;
+const REPOSITION_RANGE: f32 = 600.0;
+const EVERY_PHASE: RangeInclusive<usize> = 0..=BOSS_PHASES.len() - 1;
+const CHAINED: RangeInclusive<usize> = 0..=0;
+const UNCHAINED: RangeInclusive<usize> = 1..=BOSS_PHASES.len() - 1;
+
+pub const BOSS_ATTACKS: &[BossAttack] = &[
+    BossAttack {
+        name: "Reposition",
+        conditions: AttackConditions {
+            max_range: REPOSITION_RANGE,
+            cooldown: 0.0,
+            phases: EVERY_PHASE,
+        },
+        weight: 0.0,
+        urgency: Some(Urgency {
+            midpoint: 15.0,
+            steepness: 0.7,
+        }),
+        action: AttackAction::Reposition,
+        eye: EyeCue::Track,
+        mirror_randomly: false,
+    },
+    BossAttack {
+        name: "Beam",
+        conditions: AttackConditions {
+            max_range: BEAM_RANGE,
+            cooldown: 0.0,
+            phases: CHAINED,
+        },
+        weight: 3.0,
+        urgency: None,
+        action: AttackAction::Lazers {
+            timing: LazerTiming::Beam,
+            spawns: &[LazerSpawn {
+                at: 0.0,
+                aim: LazerAim::AtPlayer { offset: 0.0 },
+                sweep: 0.0,
+            }],
+        },
+        eye: EyeCue::Hold,
+        mirror_randomly: false,
+    },
+    BossAttack {
+        name: "Beam",
+        conditions: AttackConditions {
+            max_range: BEAM_RANGE,
+            cooldown: 0.0,
+            phases: UNCHAINED,
+        },
+        weight: 1.0,
+        urgency: None,
+        action: AttackAction::Lazers {
+            timing: LazerTiming::Beam,
+            spawns: &[LazerSpawn {
+                at: 0.0,
+                aim: LazerAim::AtPlayer { offset: 0.0 },
+                sweep: 0.0,
+            }],
+        },
+        eye: EyeCue::Hold,
+        mirror_randomly: false,
+    },
+    BossAttack {
+        name: "Sky Rain",
+        conditions: AttackConditions {
+            max_range: f32::INFINITY,
+            cooldown: 0.0,
+            phases: EVERY_PHASE,
+        },
+        weight: 1.0,
+        urgency: None,
+        action: AttackAction::Lazers {
+            timing: LazerTiming::SkyRain,
+            spawns: &[LazerSpawn {
+                at: 0.0,
+                aim: LazerAim::RainAroundPlayer { sigma: 200.0 },
+                sweep: 0.0,
+            }],
+        },
+        eye: EyeCue::LookUp,
+        mirror_randomly: false,
+    },
+    BossAttack {
+        name: "Sweep",
+        conditions: AttackConditions {
+            max_range: BEAM_RANGE,
+            cooldown: 0.0,
+            phases: UNCHAINED,
+        },
+        weight: 2.0,
+        urgency: None,
+        action: AttackAction::Lazers {
+            timing: LazerTiming::Beam,
+            spawns: &[LazerSpawn {
+                at: 0.0,
+                aim: LazerAim::AtPlayer {
+                    offset: -SWEEP_ANGLE / 2.0,
+                },
+                sweep: SWEEP_ANGLE,
+            }],
+        },
+        eye: EyeCue::Hold,
+        mirror_randomly: true,
+    },
+    BossAttack {
+        name: "Ring",
+        conditions: AttackConditions {
+            max_range: f32::INFINITY,
+            cooldown: 8.0,
+            phases: UNCHAINED,
+        },
+        weight: 1.5,
+        urgency: None,
+        action: AttackAction::Lazers {
+            timing: LazerTiming::Ring,
+            spawns: &[LazerSpawn {
+                at: 0.0,
+                aim: LazerAim::Ring {
+                    count: RING_LAZER_COUNT,
+                },
+                sweep: RING_SWEEP_ANGLE,
+            }],
+        },
+        eye: EyeCue::Hold,
+        mirror_randomly: true,
+    },
+];
+
+impl BossAttack {
+    /// How long the boss warns the player before the attack starts.
+    pub fn telegraph(&self, tuning: &BossTuning) -> f32 {
+        match self.action {
+            AttackAction::Reposition => 0.0,
+            AttackAction::Lazers { timing, .. } => timing.of(tuning).telegraph,
+        }
+    }
+}
+
+impl LazerTiming {
+    /// The timings this refers to in `tuning`.
+    pub fn of(self, tuning: &BossTuning) -> &LazerAttackTuning {
+        match self {
+            Self::Beam => &tuning.beam,
+            Self::Ring => &tuning.ring,
+            Self::SkyRain => &tuning.sky_rain,
+        }
+    }
+}
+
+impl Urgency {
+    /// Whether the attack goes off this frame, for a `roll` between 0 and 1.
+    pub fn rolls(&self, roll: f32, time_since_attack: f32, delta_time: f32) -> bool {
+        let odds = 1.0 / (1.0 + (-self.steepness * (time_since_attack - self.midpoint)).exp());
+        roll.powf(1.0 / delta_time) > 1.0 - odds
+    }
+}
+
+impl LazerSpawn {
+    /// Whether this spawn goes off between `previous` and `now` seconds into the attack.
+    /// It repeats every `every` seconds, `density` times as often.
+    pub fn fires(&self, every: Option<f32>, previous: f32, now: f32, density: f32) -> bool {
+        if now < self.at {
+            return false;
+        }
+        if previous < self.at {
+            return true;
+        }
+        every.map(|every| every / density).is_some_and(|every| {
+            ((now - self.at) / every).floor() > ((previous - self.at) / every).floor()
+        })
+    }
+}
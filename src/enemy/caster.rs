@@ -0,0 +1,223 @@
+//! A ranged enemy that keeps its distance and fires slow bolts the player can knock back.
+
+use avian2d::{math::*, prelude::*};
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    asset_tracking::LoadResource,
+    collision_layers::enemy_hurt_boxes,
+    enemy::{
+        behaviour::{AddStateMachine, EnemyAiSystems, EnemyState, StateMachine},
+        configs::*,
+        crowd::{AttackTokens, CrowdMember},
+        elite::Hasted,
+        loot::LootTable,
+        stuck::StuckDetector,
+    },
+    health::{Health, health_bar, hurtbox_prefab},
+    physics::creature::{CreaturePhysicsBundle, Grounded},
+    player::character::Player,
+    projectile::{Projectile, projectile, projectile_hitbox},
+    rng::GameRng,
+    screens::Screen,
+    script::spawning::Materializing,
+    tuning::{EnemyTuning, Tuning},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CasterAssets>();
+    app.add_state_machine::<CasterState>();
+    app.load_resource::<CasterAssets>()
+        .add_systems(Update, enemy_decision_making.in_set(EnemyAiSystems::Act));
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CasterAssets {
+    #[dependency]
+    caster: Handle<Image>,
+    #[dependency]
+    bolt: Handle<Image>,
+}
+
+impl FromWorld for CasterAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            caster: assets.load("images/caster.png"),
+            bolt: assets.load("images/caster_bolt.png"),
+        }
+    }
+}
+
+pub fn caster(
+    caster_assets: &CasterAssets,
+    translation: Vec3,
+    rng: &mut impl Rng,
+    tuning: &EnemyTuning,
+) -> impl Bundle {
+    let scale = Vec2::splat(CASTER_SCALE);
+    (
+        Name::new("Caster"),
+        Transform::from_scale(scale.extend(1.0)).with_translation(translation),
+        Sprite {
+            image: caster_assets.caster.clone(),
+            ..default()
+        },
+        CasterController,
+        StateMachine::new(CasterState::Stalk)
+            .with_timeout(rng.gen_range(CASTER_CAST_COOLDOWN), CasterState::Windup),
+        CreaturePhysicsBundle::new(
+            Collider::capsule(40.0, 90.0),
+            scale,
+            CASTER_MOVEMENT_DAMPING,
+            MAX_SLOPE_ANGLE,
+        ),
+        Health::new(tuning.caster.health),
+        Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
+        Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
+        ColliderDensity(2.0),
+        StuckDetector::new(tuning.caster.max_x_velocity),
+        CrowdMember,
+        LootTable(CASTER_LOOT),
+        children![
+            hurtbox_prefab(
+                Collider::capsule(45.0, 95.0),
+                enemy_hurt_boxes(),
+                0.5,
+                Transform::default()
+            ),
+            health_bar(Transform::from_xyz(-40., 120., 1.), Vec2::new(80.0, 5.0))
+        ],
+    )
+}
+
+#[derive(Component)]
+pub struct CasterController;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CasterState {
+    /// Keeping its distance from the player.
+    Stalk,
+    /// Standing still, about to cast.
+    Windup,
+    /// Firing a bolt at the player.
+    Cast,
+}
+
+impl EnemyState for CasterState {
+    fn is_telegraph(&self) -> bool {
+        matches!(self, Self::Windup)
+    }
+}
+
+/// Where bolts come out of, relative to the caster.
+const BOLT_OFFSET: Vec3 = Vec3::new(0.0, 40.0, 0.0);
+
+/// A bolt the player can reflect back with their weapon.
+pub fn caster_bolt(
+    caster_assets: &CasterAssets,
+    translation: Vec3,
+    velocity: Vec2,
+    damage: f32,
+) -> impl Bundle {
+    (
+        Name::new("Caster Bolt"),
+        Sprite {
+            image: caster_assets.bolt.clone(),
+            custom_size: Some(Vec2::splat(40.0)),
+            ..default()
+        },
+        Transform::from_translation(translation),
+        projectile(
+            Projectile::new(CASTER_BOLT_LIFETIME).reflectable(),
+            velocity,
+        ),
+        children![projectile_hitbox(
+            Collider::circle(16.0),
+            damage,
+            Transform::default(),
+        )],
+        StateScoped(Screen::Gameplay),
+    )
+}
+
+fn enemy_decision_making(
+    mut commands: Commands,
+    time: Res<Time>,
+    caster_assets: Res<CasterAssets>,
+    mut rng: ResMut<GameRng>,
+    mut tokens: AttackTokens,
+    tuning: Res<Tuning>,
+    target: Single<&Transform, With<Player>>,
+    mut casters: Query<
+        (
+            &mut StateMachine<CasterState>,
+            &Transform,
+            &mut LinearVelocity,
+            &mut Sprite,
+            Has<Grounded>,
+            Option<&Hasted>,
+        ),
+        (
+            With<CasterController>,
+            Without<Player>,
+            Without<Materializing>,
+        ),
+    >,
+) {
+    let delta_time = time.delta_secs_f64().adjust_precision();
+    let caster_tuning = &tuning.enemy.caster;
+    for (mut machine, pos, mut velocity, mut sprite, is_grounded, hasted) in &mut casters {
+        let max_x_velocity = caster_tuning.max_x_velocity * Hasted::factor(hasted);
+        let target_length = target.translation.x - pos.translation.x;
+        sprite.flip_x = target_length < 0.0;
+
+        if machine.just_entered() {
+            match machine.state() {
+                CasterState::Stalk => {
+                    let cooldown = rng.ai.gen_range(CASTER_CAST_COOLDOWN);
+                    machine.transition_after(cooldown, CasterState::Windup);
+                }
+                // Wait for the next chance if too many others are attacking.
+                CasterState::Windup if !tokens.try_take() => {
+                    machine.transition(CasterState::Stalk);
+                }
+                CasterState::Windup => {
+                    machine.transition_after(CASTER_WINDUP_DURATION, CasterState::Cast);
+                }
+                CasterState::Cast => {
+                    let origin = pos.translation + BOLT_OFFSET;
+                    let aim = (target.translation - origin)
+                        .truncate()
+                        .normalize_or(Vec2::X);
+                    commands.spawn(caster_bolt(
+                        &caster_assets,
+                        origin.with_z(5.0),
+                        aim * caster_tuning.bolt_speed,
+                        caster_tuning.bolt_damage,
+                    ));
+                    machine.transition(CasterState::Stalk);
+                }
+            }
+        }
+
+        if !is_grounded {
+            continue;
+        }
+        let distance = target_length.abs();
+        let direction = match machine.state() {
+            CasterState::Stalk if distance < caster_tuning.min_distance => -target_length.signum(),
+            CasterState::Stalk if distance > caster_tuning.max_distance => target_length.signum(),
+            _ => 0.0,
+        };
+        if direction != 0.0 {
+            velocity.x = (velocity.x + direction * CASTER_MOVEMENT_ACCELERATION * delta_time)
+                .clamp(-max_x_velocity, max_x_velocity);
+        } else {
+            velocity.x -= velocity.x.signum()
+                * (CASTER_MOVEMENT_ACCELERATION * delta_time).min(velocity.x.abs());
+        }
+    }
+}
@@ -1,26 +1,244 @@
 use avian2d::math::Scalar;
-use bevy::math::Vec2;
+use bevy::{color::Color, math::Vec2};
+
+use crate::enemy::loot::{Loot, LootDrop};
 
-pub const JUMP_IMPULSE: f32 = 1100.0;
 pub const MOVEMENT_DAMPING: f32 = 0.0;
 pub const MAX_SLOPE_ANGLE: f32 = (30.0 as Scalar).to_radians();
-pub const BOSS_HEALTH: f32 = 5000.0;
-pub const BOSS_TIME_BETWEEN_ATTACKS: f32 = 3.0;
-pub const TIME_TO_REPOSITION: f32 = 3.5;
 pub const POSITION_1: Vec2 = Vec2::new(-1050.0, 175.0);
 pub const POSITION_2_X: f32 = -POSITION_1.x;
 pub const MAX_REPOSITIONING_Y: f32 = 800.0;
-pub const SKY_LAZER_DURATION: f32 = 2.;
-pub const SKY_LAZER_SPAWN_FREQUENCY: f32 = 0.3;
-pub const SKY_ATTACK_DURATION: f32 = 5.0;
-pub const SKY_ATTACK_START_TIME: f32 = 0.25;
-pub const BEAM_LAZER_DURATION: f32 = 1.65;
 // pub const BEAM_LAZER_WIDTH: f32 = 2.;
-pub const BEAM_ATTACK_DURATION: f32 = BEAM_LAZER_DURATION + 0.35;
+/// How far a sweeping beam turns while it is out.
+pub const SWEEP_ANGLE: f32 = (100.0 as Scalar).to_radians();
+pub const RING_LAZER_COUNT: usize = 8;
+/// How far the ring of lasers turns while it is out.
+pub const RING_SWEEP_ANGLE: f32 = 1.05;
+/// How long the eye takes to break its chains between phases. It can't be hurt meanwhile.
+pub const BOSS_UNCHAIN_DURATION: f32 = 2.0;
+pub const CHAIN_SHARD_COUNT: usize = 14;
+/// How long the music of one boss phase takes to fade into the next.
+pub const BOSS_MUSIC_CROSSFADE: f32 = 1.5;
+
+pub struct BossPhaseConfig {
+    /// The phase starts once the boss is at or below this fraction of its max health.
+    pub health_threshold: f32,
+    /// Relative to the `assets` folder. The level music covers the first phase.
+    pub music: &'static str,
+    /// Playback speed of the boss music.
+    pub music_speed: f32,
+}
+
+pub const BOSS_PHASES: [BossPhaseConfig; 2] = [
+    BossPhaseConfig {
+        health_threshold: 1.0,
+        music: "audio/music/boss.ogg",
+        music_speed: 1.0,
+    },
+    BossPhaseConfig {
+        health_threshold: 0.5,
+        music: "audio/music/boss.ogg",
+        music_speed: 1.15,
+    },
+];
 //slime stuff
-pub const RED_HEALTH: f32 = 25.0;
-pub const RED_JUMP_ATTACK_COOLDOWN: f32 = 2.0;
-pub const RED_MAX_X_VELOCITY: f32 = 325.0;
-pub const BLACK_HEALTH: f32 = 40.0;
-pub const BLACK_JUMP_ATTACK_COOLDOWN: f32 = 3.5;
-pub const BLACK_MAX_X_VELOCITY: f32 = 250.0;
+pub const BOTLEFT_SPAWN: Vec2 = Vec2::new(-500.0, 100.0);
+pub const BOTRIGHT_SPAWN: Vec2 = Vec2::new(500.0, 100.0);
+pub const TOPLEFT_SPAWN: Vec2 = Vec2::new(-500.0, 200.0);
+pub const TOPRIGHT_SPAWN: Vec2 = Vec2::new(500.0, 200.0);
+/// Spawns above solid ground, used to put stuck enemies back into play.
+pub const RELOCATION_SPAWNS: [Vec2; 4] =
+    [BOTLEFT_SPAWN, BOTRIGHT_SPAWN, TOPLEFT_SPAWN, TOPRIGHT_SPAWN];
+/// How long an enemy can go without a way to the player or getting hurt before it counts as
+/// stuck.
+pub const STUCK_RELOCATE_SECONDS: f32 = 10.0;
+/// How a kind of slime looks and what it drops. Its balance numbers are in
+/// [`SlimeTuning`](crate::tuning::SlimeTuning).
+pub struct SlimeStats {
+    pub scale: f32,
+    /// Multiplied with the sprite, to tell kinds that share an image apart.
+    pub tint: Color,
+    /// How much of a hit gets through, unless it's a downward attack from above.
+    pub damage_taken: f32,
+    pub loot: &'static [LootDrop],
+}
+
+pub const BLACK_SLIME: SlimeStats = SlimeStats {
+    scale: 0.5,
+    tint: Color::WHITE,
+    damage_taken: 1.0,
+    loot: COMMON_LOOT,
+};
+pub const RED_SLIME: SlimeStats = SlimeStats {
+    scale: 0.5,
+    tint: Color::WHITE,
+    damage_taken: 1.0,
+    loot: COMMON_LOOT,
+};
+pub const SPLITTING_SLIME: SlimeStats = SlimeStats {
+    scale: 0.65,
+    tint: Color::srgb(0.55, 1.0, 0.55),
+    damage_taken: 1.0,
+    loot: COMMON_LOOT,
+};
+/// What a splitting slime splits into.
+pub const SPLITLING_SLIME: SlimeStats = SlimeStats {
+    scale: 0.3,
+    tint: Color::srgb(0.55, 1.0, 0.55),
+    damage_taken: 1.0,
+    loot: SCARCE_LOOT,
+};
+/// Jumps again as soon as it lands.
+pub const BOUNCER_SLIME: SlimeStats = SlimeStats {
+    scale: 0.45,
+    tint: Color::srgb(0.5, 0.7, 1.0),
+    damage_taken: 1.0,
+    loot: COMMON_LOOT,
+};
+pub const ARMORED_SLIME: SlimeStats = SlimeStats {
+    scale: 0.55,
+    tint: Color::srgb(0.7, 0.7, 0.8),
+    damage_taken: 0.25,
+    loot: RICH_LOOT,
+};
+/// How far apart the two halves of a splitting slime land.
+pub const SPLIT_OFFSET_X: f32 = 40.0;
+/// Sideways and upwards speed the halves of a splitting slime pop out with.
+pub const SPLIT_VELOCITY: Vec2 = Vec2::new(250.0, 500.0);
+/// How long a slime winds up before jumping.
+pub const SLIME_WINDUP_DURATION: f32 = 0.2;
+//imp stuff
+pub const IMP_SCALE: f32 = 0.6;
+pub const IMP_GRAVITY_SCALE: f32 = 1.5;
+pub const IMP_MOVEMENT_DAMPING: f32 = 2.0;
+pub const IMP_MOVEMENT_ACCELERATION: f32 = 1500.0;
+pub const IMP_STOP_RUNNING_DISTANCE: f32 = 90.0;
+/// How long the swing hitbox stays out.
+pub const IMP_MELEE_SWING_DURATION: f32 = 0.2;
+//wisp stuff
+pub const WISP_SCALE: f32 = 0.8;
+pub const WISP_MAX_ACCELERATION: f32 = 900.0;
+/// How far from the player a hovering wisp tries to stay.
+pub const WISP_PREFERRED_DISTANCE: f32 = 350.0;
+/// A hovering wisp slows down within this distance of where it wants to be.
+pub const WISP_ARRIVE_RADIUS: f32 = 150.0;
+/// Seconds a wisp hovers between swoops, picked at random.
+pub const WISP_HOVER_TIME: std::ops::Range<f32> = 2.0..4.0;
+pub const WISP_WINDUP_DURATION: f32 = 0.4;
+pub const WISP_SWOOP_DURATION: f32 = 0.7;
+/// Wisps closer to each other than this push apart.
+pub const WISP_SEPARATION_RADIUS: f32 = 160.0;
+/// How strongly separation steers compared to everything else.
+pub const WISP_SEPARATION_WEIGHT: f32 = 1.5;
+//caster stuff
+pub const CASTER_SCALE: f32 = 0.8;
+pub const CASTER_MOVEMENT_DAMPING: f32 = 4.0;
+pub const CASTER_MOVEMENT_ACCELERATION: f32 = 900.0;
+/// Seconds between casts, picked at random.
+pub const CASTER_CAST_COOLDOWN: std::ops::Range<f32> = 2.5..4.0;
+pub const CASTER_WINDUP_DURATION: f32 = 0.6;
+pub const CASTER_BOLT_LIFETIME: f32 = 6.0;
+//navigation stuff
+/// How far from a platform's edge enemies take off and land.
+pub const NAV_EDGE_MARGIN: f32 = 40.0;
+/// How far past the edge of the platform they are jumping onto enemies land.
+pub const NAV_LANDING_MARGIN: f32 = 70.0;
+/// How far above the platform they are jumping onto enemies aim their apex.
+pub const NAV_APEX_CLEARANCE: f32 = 40.0;
+/// Half the width of the widest enemy that follows the navigation graph.
+pub const NAV_BODY_RADIUS: f32 = 40.0;
+/// How close to a takeoff point an enemy has to be to take the jump.
+pub const NAV_TAKEOFF_TOLERANCE: f32 = 50.0;
+//crowd stuff
+/// Ground enemies closer than this horizontally push apart.
+pub const ENEMY_SEPARATION_RADIUS: f32 = 90.0;
+/// How fast two enemies right on top of each other move apart.
+pub const ENEMY_SEPARATION_SPEED: f32 = 250.0;
+/// How many enemies can start an attack within [`ATTACK_TOKEN_WINDOW`] seconds.
+pub const ATTACK_TOKENS: usize = 2;
+pub const ATTACK_TOKEN_WINDOW: f32 = 1.0;
+//elite stuff
+/// How much faster hasted elites move and recover from attacks.
+pub const ELITE_HASTE: f32 = 1.5;
+/// The share of damage dealt that vampiric elites heal.
+pub const ELITE_VAMPIRIC_HEAL: f32 = 0.5;
+pub const ELITE_EXPLOSION_RADIUS: f32 = 200.0;
+pub const ELITE_EXPLOSION_DAMAGE: f32 = 15.0;
+/// How long the explosion of an explosive elite stays around to hurt things.
+pub const ELITE_EXPLOSION_DURATION: f32 = 0.3;
+/// How much damage a shielded elite absorbs before its health goes down.
+pub const ELITE_SHIELD: f32 = 30.0;
+/// In the elite's local space, so it grows and shrinks with the enemy.
+pub const ELITE_AURA_RADIUS: f32 = 90.0;
+/// Width of the ring around the aura for each modifier after the first.
+pub const ELITE_AURA_RING_WIDTH: f32 = 14.0;
+/// How much bigger an elite's health bar is than a normal one.
+pub const ELITE_HEALTH_BAR_SCALE: f32 = 1.5;
+//spawn stuff
+/// How long the portal shows where an enemy is about to appear.
+pub const ENEMY_SPAWN_TELEGRAPH: f32 = 1.2;
+/// Portals stay open up to this much shorter or longer, so enemies spawned together don't all
+/// come through on the same frame.
+pub const SPAWN_TELEGRAPH_JITTER: f32 = 0.15;
+/// How long a new enemy takes to fade in, harmless, after its portal closes.
+pub const ENEMY_MATERIALIZE_DURATION: f32 = 0.6;
+pub const SPAWN_PORTAL_SIZE: Vec2 = Vec2::new(160.0, 50.0);
+//loot stuff
+pub const COMMON_LOOT: &[LootDrop] = &[
+    LootDrop {
+        loot: Loot::HealthOrb,
+        chance: 0.25,
+    },
+    LootDrop {
+        loot: Loot::FuryShard,
+        chance: 0.2,
+    },
+];
+/// For enemies that come in numbers, like splitlings.
+pub const SCARCE_LOOT: &[LootDrop] = &[
+    LootDrop {
+        loot: Loot::HealthOrb,
+        chance: 0.08,
+    },
+    LootDrop {
+        loot: Loot::FuryShard,
+        chance: 0.08,
+    },
+];
+/// For enemies that take more effort to kill.
+pub const RICH_LOOT: &[LootDrop] = &[
+    LootDrop {
+        loot: Loot::HealthOrb,
+        chance: 0.5,
+    },
+    LootDrop {
+        loot: Loot::FuryShard,
+        chance: 0.4,
+    },
+];
+pub const IMP_LOOT: &[LootDrop] = COMMON_LOOT;
+pub const WISP_LOOT: &[LootDrop] = COMMON_LOOT;
+pub const CASTER_LOOT: &[LootDrop] = RICH_LOOT;
+/// Seconds before a drop nobody picked up disappears.
+pub const LOOT_LIFETIME: f32 = 10.0;
+/// Drops blink for this long before they disappear.
+pub const LOOT_BLINK_TIME: f32 = 2.5;
+/// Drops closer than this to the player fly towards them.
+pub const LOOT_MAGNET_RADIUS: f32 = 250.0;
+pub const LOOT_MAGNET_SPEED: f32 = 800.0;
+pub const LOOT_PICKUP_RADIUS: f32 = 50.0;
+/// How fast drops pop out of an enemy.
+pub const LOOT_POP_SPEED: f32 = 450.0;
+pub const HEALTH_ORB_HEAL: f32 = 10.0;
+//hit reaction stuff
+pub const HIT_FLASH_DURATION: f32 = 0.1;
+/// Brighter than white, so the flash shows up on dark sprites too.
+pub const HIT_FLASH_COLOR: Color = Color::linear_rgb(5.0, 5.0, 5.0);
+/// Hits doing at least this much damage flash [`HEAVY_HIT_FLASH_COLOR`] instead.
+pub const HEAVY_HIT_DAMAGE: f32 = 20.0;
+pub const HEAVY_HIT_FLASH_COLOR: Color = Color::linear_rgb(4.0, 0.4, 0.4);
+pub const HURT_SQUASH_DURATION: f32 = 0.2;
+/// How much an enemy gets squashed along the direction it was hit from, and stretched across it.
+pub const HURT_SQUASH_AMOUNT: f32 = 0.2;
+/// How fast a hit knocks an enemy back.
+pub const HURT_KNOCKBACK_SPEED: f32 = 250.0;
@@ -0,0 +1,124 @@
+//! Keeps groups of enemies from stacking up and from all attacking at once.
+//!
+//! Ground enemies with a [`CrowdMember`] push apart when they get too close. Enemies ask
+//! [`AttackTokens`] before starting an attack, which only allows so many attacks in a short
+//! window. Both are tuned through [`CrowdSettings`].
+
+use std::collections::VecDeque;
+
+use avian2d::prelude::LinearVelocity;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    PausableSystems,
+    enemy::{behaviour::EnemyAiSystems, configs::*},
+    physics::creature::Grounded,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CrowdSettings>();
+    app.init_resource::<RecentAttacks>();
+    app.add_systems(
+        Update,
+        separate_enemies
+            .after(EnemyAiSystems::Act)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct CrowdSettings {
+    pub separation_radius: f32,
+    pub separation_speed: f32,
+    /// How many attacks can start within `attack_window` seconds.
+    pub attack_tokens: usize,
+    pub attack_window: f32,
+}
+
+impl Default for CrowdSettings {
+    fn default() -> Self {
+        Self {
+            separation_radius: ENEMY_SEPARATION_RADIUS,
+            separation_speed: ENEMY_SEPARATION_SPEED,
+            attack_tokens: ATTACK_TOKENS,
+            attack_window: ATTACK_TOKEN_WINDOW,
+        }
+    }
+}
+
+/// Ground enemies that push apart from each other.
+#[derive(Component, Default)]
+pub struct CrowdMember;
+
+/// When the latest attacks started, oldest first.
+#[derive(Resource, Default)]
+struct RecentAttacks(VecDeque<f32>);
+
+/// Limits how many enemies can start an attack at about the same time.
+#[derive(SystemParam)]
+pub struct AttackTokens<'w> {
+    settings: Res<'w, CrowdSettings>,
+    recent: ResMut<'w, RecentAttacks>,
+    time: Res<'w, Time>,
+}
+
+impl AttackTokens<'_> {
+    /// Takes a token if one is free. Only call this right before actually attacking.
+    pub fn try_take(&mut self) -> bool {
+        let now = self.time.elapsed_secs();
+        let window = self.settings.attack_window;
+        let recent = &mut self.recent.0;
+        while recent.front().is_some_and(|started| now - started > window) {
+            recent.pop_front();
+        }
+        if recent.len() >= self.settings.attack_tokens {
+            return false;
+        }
+        recent.push_back(now);
+        true
+    }
+}
+
+/// Moves overlapping enemies apart sideways. Raises their speed away from each other rather than
+/// adding to it, so it works on enemies whose AI holds them still without building up. Enemies in
+/// the air are left alone, so they can't be pushed through platforms.
+fn separate_enemies(
+    settings: Res<CrowdSettings>,
+    mut members: Query<
+        (Entity, &Transform, &mut LinearVelocity),
+        (With<CrowdMember>, With<Grounded>),
+    >,
+) {
+    let radius = settings.separation_radius;
+    let positions: Vec<(Entity, Vec2)> = members
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+        .collect();
+
+    for (entity, transform, mut velocity) in &mut members {
+        let position = transform.translation.truncate();
+        let push: f32 = positions
+            .iter()
+            .filter(|(other, at)| *other != entity && (at.y - position.y).abs() < radius)
+            .map(|(other, at)| {
+                let dx = position.x - at.x;
+                if dx.abs() >= radius {
+                    return 0.0;
+                }
+                // Right on top of each other, split them up in a consistent way.
+                let direction = if dx == 0.0 {
+                    if entity > *other { 1.0 } else { -1.0 }
+                } else {
+                    dx.signum()
+                };
+                direction * (1.0 - dx.abs() / radius)
+            })
+            .sum();
+        let separation = push * settings.separation_speed;
+        if separation > 0.0 {
+            velocity.x = velocity.x.max(separation);
+        } else if separation < 0.0 {
+            velocity.x = velocity.x.min(separation);
+        }
+    }
+}
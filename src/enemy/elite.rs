@@ -0,0 +1,230 @@
+//! Modifiers that turn any enemy into a tougher elite version of itself.
+//!
+//! Insert [`Elite`] with a list of [`EliteModifier`]s on a spawned enemy and it gets the
+//! components for each modifier, an aura in the colour of its first modifier with a ring around
+//! it for each of the others, and a bigger health bar.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    collision_layers::enemy_hit_boxes,
+    enemy::{configs::*, slime::kill_everything_that_dies},
+    health::{ChangeHpEvent, DeathEvent, HealthBarFrame, HitLanded, Shield, hitbox_prefab},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EliteAssets>();
+    app.add_systems(
+        Update,
+        (apply_elite_modifiers, heal_vampires, tick_explosions).in_set(PausableSystems),
+    );
+    app.add_systems(
+        Last,
+        explode_elites
+            .before(kill_everything_that_dies)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EliteModifier {
+    /// Moves faster and recovers from attacks sooner.
+    Hasted,
+    /// Heals some of the damage it deals.
+    Vampiric,
+    /// Blows up when it dies.
+    Explosive,
+    /// Absorbs the first [`ELITE_SHIELD`] damage it takes.
+    Shielded,
+}
+
+impl EliteModifier {
+    fn color(self) -> Color {
+        match self {
+            Self::Hasted => Color::srgb(1.0, 0.85, 0.2),
+            Self::Vampiric => Color::srgb(0.75, 0.0, 0.25),
+            Self::Explosive => Color::srgb(1.0, 0.4, 0.0),
+            Self::Shielded => Color::srgb(0.3, 0.7, 1.0),
+        }
+    }
+}
+
+/// The modifiers of an elite enemy. Applied once, when this is added.
+#[derive(Component, Clone, Debug)]
+pub struct Elite(pub Vec<EliteModifier>);
+
+/// Speeds up the enemy's movement and its state machine by the given factor.
+#[derive(Component)]
+pub struct Hasted(pub f32);
+
+impl Hasted {
+    /// The speed factor of an enemy that might be hasted.
+    pub fn factor(hasted: Option<&Self>) -> f32 {
+        hasted.map_or(1.0, |hasted| hasted.0)
+    }
+}
+
+#[derive(Component)]
+pub struct Vampiric;
+
+#[derive(Component)]
+pub struct Explosive;
+
+/// The area hitbox left behind by an [`Explosive`] elite.
+#[derive(Component)]
+struct Explosion {
+    time_remaining: f32,
+}
+
+#[derive(Resource)]
+struct EliteAssets {
+    aura: Handle<Mesh>,
+    aura_ring: Handle<Mesh>,
+    explosion: Handle<Mesh>,
+    hasted: Handle<ColorMaterial>,
+    vampiric: Handle<ColorMaterial>,
+    explosive: Handle<ColorMaterial>,
+    shielded: Handle<ColorMaterial>,
+    explosion_material: Handle<ColorMaterial>,
+}
+
+impl FromWorld for EliteAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let aura = meshes.add(Circle::new(ELITE_AURA_RADIUS));
+        let aura_ring = meshes.add(Annulus::new(
+            ELITE_AURA_RADIUS,
+            ELITE_AURA_RADIUS + ELITE_AURA_RING_WIDTH,
+        ));
+        let explosion = meshes.add(Circle::new(ELITE_EXPLOSION_RADIUS));
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        let aura_material = |modifier: EliteModifier| modifier.color().with_alpha(0.35);
+        Self {
+            aura,
+            aura_ring,
+            explosion,
+            hasted: materials.add(aura_material(EliteModifier::Hasted)),
+            vampiric: materials.add(aura_material(EliteModifier::Vampiric)),
+            explosive: materials.add(aura_material(EliteModifier::Explosive)),
+            shielded: materials.add(aura_material(EliteModifier::Shielded)),
+            explosion_material: materials.add(EliteModifier::Explosive.color().with_alpha(0.6)),
+        }
+    }
+}
+
+impl EliteAssets {
+    fn aura_material(&self, modifier: EliteModifier) -> Handle<ColorMaterial> {
+        match modifier {
+            EliteModifier::Hasted => self.hasted.clone(),
+            EliteModifier::Vampiric => self.vampiric.clone(),
+            EliteModifier::Explosive => self.explosive.clone(),
+            EliteModifier::Shielded => self.shielded.clone(),
+        }
+    }
+}
+
+fn apply_elite_modifiers(
+    mut commands: Commands,
+    elite_assets: Res<EliteAssets>,
+    elites: Query<(Entity, &Elite, Option<&Children>), Added<Elite>>,
+    mut health_bars: Query<&mut Transform, With<HealthBarFrame>>,
+) {
+    for (entity, elite, children) in &elites {
+        let mut entity_commands = commands.entity(entity);
+        for modifier in &elite.0 {
+            match modifier {
+                EliteModifier::Hasted => entity_commands.insert(Hasted(ELITE_HASTE)),
+                EliteModifier::Vampiric => entity_commands.insert(Vampiric),
+                EliteModifier::Explosive => entity_commands.insert(Explosive),
+                EliteModifier::Shielded => entity_commands.insert(Shield {
+                    remaining: ELITE_SHIELD,
+                }),
+            };
+        }
+
+        for (i, &modifier) in elite.0.iter().enumerate() {
+            let (mesh, scale) = match i {
+                0 => (elite_assets.aura.clone(), 1.0),
+                // Each ring goes around the one before it.
+                _ => (
+                    elite_assets.aura_ring.clone(),
+                    1.0 + (i - 1) as f32 * ELITE_AURA_RING_WIDTH / ELITE_AURA_RADIUS,
+                ),
+            };
+            entity_commands.with_child((
+                Name::new("Elite Aura"),
+                Mesh2d(mesh),
+                MeshMaterial2d(elite_assets.aura_material(modifier)),
+                // Behind the enemy's sprite.
+                Transform::from_xyz(0.0, 0.0, -0.1).with_scale(Vec3::splat(scale)),
+            ));
+        }
+
+        // Grow the health bar around its middle, which is where it's usually centered.
+        let mut health_bars = health_bars.iter_many_mut(children.into_iter().flatten());
+        while let Some(mut transform) = health_bars.fetch_next() {
+            transform.translation.x *= ELITE_HEALTH_BAR_SCALE;
+            transform.scale *= Vec2::splat(ELITE_HEALTH_BAR_SCALE).extend(1.0);
+        }
+    }
+}
+
+fn heal_vampires(
+    mut hit_landed: EventReader<HitLanded>,
+    mut change_hp: EventWriter<ChangeHpEvent>,
+    vampires: Query<(), With<Vampiric>>,
+) {
+    for hit in hit_landed.read() {
+        if vampires.contains(hit.attacker) {
+            change_hp.write(ChangeHpEvent::new(
+                hit.attacker,
+                hit.damage * ELITE_VAMPIRIC_HEAL,
+            ));
+        }
+    }
+}
+
+fn explode_elites(
+    mut commands: Commands,
+    mut death_reader: EventReader<DeathEvent>,
+    elite_assets: Res<EliteAssets>,
+    explosives: Query<&GlobalTransform, With<Explosive>>,
+) {
+    for DeathEvent(entity) in death_reader.read() {
+        let Ok(transform) = explosives.get(*entity) else {
+            continue;
+        };
+        commands.spawn((
+            Name::new("Elite Explosion"),
+            Explosion {
+                time_remaining: ELITE_EXPLOSION_DURATION,
+            },
+            hitbox_prefab(
+                Collider::circle(ELITE_EXPLOSION_RADIUS),
+                enemy_hit_boxes(),
+                ELITE_EXPLOSION_DURATION,
+                ELITE_EXPLOSION_DAMAGE,
+                Transform::from_translation(transform.translation().with_z(5.0)),
+            ),
+            Mesh2d(elite_assets.explosion.clone()),
+            MeshMaterial2d(elite_assets.explosion_material.clone()),
+            StateScoped(Screen::Gameplay),
+        ));
+    }
+}
+
+fn tick_explosions(
+    mut commands: Commands,
+    time: Res<Time>,
+    explosions: Query<(Entity, &mut Explosion)>,
+) {
+    for (entity, mut explosion) in explosions {
+        explosion.time_remaining -= time.delta_secs();
+        if explosion.time_remaining < 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
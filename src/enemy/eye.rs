@@ -8,7 +8,11 @@ use crate::{
     animation::reversible_animation,
     asset_tracking::LoadResource,
     collision_layers::{enemy_hit_boxes, enemy_hurt_boxes},
-    enemy::boss::BossController,
+    enemy::{
+        behaviour::StateMachine,
+        boss::{BossController, BossState},
+        boss_attacks::EyeCue,
+    },
     health::{health_bar, hitbox_prefab, hurtbox_prefab},
     player::character::Player,
 };
@@ -180,9 +184,12 @@ fn update_eye_animation(
         Without<Player>,
     >,
     player: Single<&Transform, With<Player>>,
-    boss: Single<&BossController>,
+    boss: Single<(&BossController, &StateMachine<BossState>)>,
     time: Res<Time>,
 ) {
+    let (boss, machine) = boss.into_inner();
+    // The ring spins loose once the chains are off.
+    let ring_speed = if boss.unchained() { 1.2 } else { 0.4 };
     for (mut sprite, mut transform, global_transform, mut animation, name) in &mut query {
         if let Some(atlas) = sprite.texture_atlas.as_mut() {
             atlas.index = animation.frame;
@@ -190,13 +197,13 @@ fn update_eye_animation(
             //Marker? I hardly know 'er
             match name.as_str() {
                 "Pupil" => {
-                    if boss.beam_lazer_remaining_duration > 0.0 {
-                        continue;
-                    }
-                    let dir = if boss.sky_lazer_remaining_duration > 0.0 {
-                        Vec2::new(0., 1.)
-                    } else {
-                        player.translation.truncate() - global_transform.translation().truncate()
+                    let dir = match machine.state().attack().map(|attack| attack.eye) {
+                        Some(EyeCue::Hold) => continue,
+                        Some(EyeCue::LookUp) => Vec2::new(0., 1.),
+                        Some(EyeCue::Track) | None => {
+                            player.translation.truncate()
+                                - global_transform.translation().truncate()
+                        }
                     };
 
                     let target = (&dir.normalize_or_zero() * 50.0).extend(1.0);
@@ -205,9 +212,11 @@ fn update_eye_animation(
                         .smooth_nudge(&target, 1.2, time.delta_secs());
                 }
                 "Ring" => {
-                    transform
-                        .rotation
-                        .smooth_nudge(&animation.target, 0.4, time.delta_secs());
+                    transform.rotation.smooth_nudge(
+                        &animation.target,
+                        ring_speed,
+                        time.delta_secs(),
+                    );
 
                     if (transform.rotation - animation.target).length() < 0.1 {
                         animation.update_target();
@@ -0,0 +1,234 @@
+//! Makes hits on enemies visible: a flash, a squash along the direction of the hit and a bit
+//! of knockback.
+//!
+//! Everything here is driven by [`HitLanded`], so enemy modules don't need to do anything to
+//! get it. The flash and the squash cover every [`Sprite`] of the enemy, including its children,
+//! apart from its health bar. The squash only resizes the sprites, never the enemy's
+//! [`Transform`], so its colliders stay the same size.
+
+use std::f32::consts::PI;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    enemy::{behaviour::SpriteTint, configs::*},
+    health::{Health, HealthBarFrame, HitLanded},
+    player::character::Player,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (react_to_hits, tick_hit_flashes, tick_squashes)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+/// Tints every sprite in `sprites` with `color` until `time_remaining` runs out.
+#[derive(Component)]
+pub struct HitFlash {
+    time_remaining: f32,
+    color: Color,
+    sprites: Vec<Entity>,
+}
+
+/// The colour of a flashing sprite to go back to.
+#[derive(Component)]
+struct FlashedSprite {
+    original: Color,
+}
+
+/// Squashes the sprites of an enemy along `direction`, and back to their size.
+#[derive(Component)]
+struct Squash {
+    elapsed: f32,
+    direction: Vec2,
+    sprites: Vec<SquashedSprite>,
+}
+
+#[derive(Clone)]
+struct SquashedSprite {
+    entity: Entity,
+    /// The `custom_size` to go back to.
+    original: Option<Vec2>,
+    size: Vec2,
+}
+
+fn react_to_hits(
+    mut commands: Commands,
+    mut hit_landed: EventReader<HitLanded>,
+    images: Res<Assets<Image>>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    mut targets: Query<
+        (
+            Option<&HitFlash>,
+            Option<&Squash>,
+            Option<&RigidBody>,
+            Option<&mut LinearVelocity>,
+        ),
+        (With<Health>, Without<Player>),
+    >,
+    children: Query<&Children>,
+    sprites: Query<(&Sprite, Has<FlashedSprite>)>,
+    health_bars: Query<(), With<HealthBarFrame>>,
+) {
+    for hit in hit_landed.read() {
+        let Ok((flash, squash, body, velocity)) = targets.get_mut(hit.target) else {
+            continue;
+        };
+
+        let flashing = match flash {
+            Some(flash) => flash.sprites.clone(),
+            None => {
+                let mut flashing = Vec::new();
+                collect_sprites(hit.target, &children, &sprites, &health_bars, &mut flashing);
+                flashing
+            }
+        };
+        for &entity in &flashing {
+            if let Ok((sprite, false)) = sprites.get(entity) {
+                commands.entity(entity).insert(FlashedSprite {
+                    original: sprite.color,
+                });
+            }
+        }
+        // Sprites still squashed from the last hit would be measured at their squashed size.
+        let squashed = match squash {
+            Some(squash) => squash.sprites.clone(),
+            None => flashing
+                .iter()
+                .filter_map(|&entity| {
+                    let (sprite, _) = sprites.get(entity).ok()?;
+                    Some(SquashedSprite {
+                        entity,
+                        original: sprite.custom_size,
+                        size: sprite_size(sprite, &images, &layouts)?,
+                    })
+                })
+                .collect(),
+        };
+        commands.entity(hit.target).insert((
+            HitFlash {
+                time_remaining: HIT_FLASH_DURATION,
+                color: if hit.damage >= HEAVY_HIT_DAMAGE {
+                    HEAVY_HIT_FLASH_COLOR
+                } else {
+                    HIT_FLASH_COLOR
+                },
+                sprites: flashing,
+            },
+            Squash {
+                elapsed: 0.0,
+                direction: hit.direction,
+                sprites: squashed,
+            },
+        ));
+
+        // Kinematic enemies move themselves, knocking them back would send them drifting.
+        if let (Some(RigidBody::Dynamic), Some(mut velocity)) = (body, velocity) {
+            velocity.0 += hit.direction * HURT_KNOCKBACK_SPEED;
+        }
+    }
+}
+
+/// How big `sprite` is drawn, if its image has loaded or it doesn't depend on it.
+fn sprite_size(
+    sprite: &Sprite,
+    images: &Assets<Image>,
+    layouts: &Assets<TextureAtlasLayout>,
+) -> Option<Vec2> {
+    sprite
+        .custom_size
+        .or_else(|| {
+            let atlas = sprite.texture_atlas.as_ref()?;
+            Some(atlas.texture_rect(layouts)?.size().as_vec2())
+        })
+        .or_else(|| images.get(&sprite.image).map(Image::size_f32))
+}
+
+/// Adds `entity` and its descendants that have a [`Sprite`] to `out`, skipping health bars.
+fn collect_sprites(
+    entity: Entity,
+    children: &Query<&Children>,
+    sprites: &Query<(&Sprite, Has<FlashedSprite>)>,
+    health_bars: &Query<(), With<HealthBarFrame>>,
+    out: &mut Vec<Entity>,
+) {
+    if health_bars.contains(entity) {
+        return;
+    }
+    if sprites.contains(entity) {
+        out.push(entity);
+    }
+    for child in children.get(entity).into_iter().flatten() {
+        collect_sprites(*child, children, sprites, health_bars, out);
+    }
+}
+
+fn tick_hit_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flashes: Query<(Entity, &mut HitFlash, Option<&SpriteTint>)>,
+    mut sprites: Query<(&mut Sprite, &FlashedSprite)>,
+) {
+    for (entity, mut flash, tint) in &mut flashes {
+        flash.time_remaining -= time.delta_secs();
+        let done = flash.time_remaining <= 0.0;
+        for &sprite_entity in &flash.sprites {
+            let Ok((mut sprite, flashed)) = sprites.get_mut(sprite_entity) else {
+                continue;
+            };
+            // Keep the alpha, something else might be fading the enemy in or out.
+            let alpha = sprite.color.alpha();
+            let color = if !done {
+                flash.color
+            } else if sprite_entity == entity {
+                // Telegraphs may have changed the tint during the flash, go back to the base.
+                tint.map_or(flashed.original, |tint| tint.0)
+            } else {
+                flashed.original
+            };
+            sprite.color = color.with_alpha(alpha);
+            if done {
+                commands.entity(sprite_entity).remove::<FlashedSprite>();
+            }
+        }
+        if done {
+            commands.entity(entity).remove::<HitFlash>();
+        }
+    }
+}
+
+fn tick_squashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut squashes: Query<(Entity, &mut Squash)>,
+    mut sprites: Query<&mut Sprite>,
+) {
+    for (entity, mut squash) in &mut squashes {
+        squash.elapsed += time.delta_secs();
+        let done = squash.elapsed >= HURT_SQUASH_DURATION;
+        let amount = (squash.elapsed / HURT_SQUASH_DURATION * PI).sin() * HURT_SQUASH_AMOUNT;
+        let factor = if squash.direction.x.abs() >= squash.direction.y.abs() {
+            Vec2::new(1.0 - amount, 1.0 + amount)
+        } else {
+            Vec2::new(1.0 + amount, 1.0 - amount)
+        };
+        for squashed in &squash.sprites {
+            let Ok(mut sprite) = sprites.get_mut(squashed.entity) else {
+                continue;
+            };
+            sprite.custom_size = if done {
+                squashed.original
+            } else {
+                Some(squashed.size * factor)
+            };
+        }
+        if done {
+            commands.entity(entity).remove::<Squash>();
+        }
+    }
+}
@@ -5,20 +5,30 @@ use bevy::{
 };
 
 use crate::{
+    PausableSystems,
     asset_tracking::LoadResource,
-    enemy::configs::*,
-    health::Health,
-    physics::{
-        configs::GRAVITY_ACCELERATION,
-        creature::{CreaturePhysicsBundle, Grounded},
+    collision_layers::{enemy_hit_boxes, enemy_hurt_boxes},
+    difficulty::Difficulty,
+    enemy::{
+        configs::*,
+        crowd::{AttackTokens, CrowdMember},
+        elite::Hasted,
+        loot::LootTable,
+        stuck::StuckDetector,
     },
-    player::movement::CharacterController,
+    health::{Health, health_bar, hitbox_prefab, hurtbox_prefab},
+    physics::creature::{CreaturePhysicsBundle, Grounded},
+    player::character::Player,
+    script::spawning::Materializing,
+    tuning::{EnemyTuning, Tuning},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<ImpAssets>();
-    app.load_resource::<ImpAssets>()
-        .add_systems(Update, enemy_decision_making);
+    app.load_resource::<ImpAssets>().add_systems(
+        Update,
+        (enemy_decision_making, tick_imp_swings).in_set(PausableSystems),
+    );
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -37,20 +47,40 @@ impl FromWorld for ImpAssets {
     }
 }
 
-pub fn imp(imp_assets: &ImpAssets, translation: Vec3) -> impl Bundle {
+pub fn imp(imp_assets: &ImpAssets, translation: Vec3, tuning: &EnemyTuning) -> impl Bundle {
+    let scale = Vec2::splat(IMP_SCALE);
     (
         Name::new("Imp"),
-        Transform::from_scale(Vec2::splat(0.5).extend(1.0)).with_translation(translation),
+        Transform::from_scale(scale.extend(1.0)).with_translation(translation),
         Sprite {
             image: imp_assets.imp.clone(),
             ..default()
         },
-        ImpControllerBundle::new(Collider::circle(30.0)),
-        Health::new(CHARACTER_HEALTH),
+        ImpControllerBundle::new(Collider::capsule(55.0, 40.0), scale),
+        Health::new(tuning.imp.health),
         Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
         Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
         ColliderDensity(2.0),
-        GravityScale(1.5),
+        GravityScale(IMP_GRAVITY_SCALE),
+        StuckDetector::new(tuning.imp.max_x_velocity),
+        CrowdMember,
+        LootTable(IMP_LOOT),
+        children![
+            hurtbox_prefab(
+                Collider::capsule(60.0, 40.0),
+                enemy_hurt_boxes(),
+                0.5,
+                Transform::default()
+            ),
+            hitbox_prefab(
+                Collider::capsule(55.0, 40.0),
+                enemy_hit_boxes(),
+                0.0,
+                tuning.imp.contact_damage,
+                Transform::default(),
+            ),
+            health_bar(Transform::from_xyz(-40., 130., 1.), Vec2::new(80.0, 5.0))
+        ],
     )
 }
 
@@ -75,27 +105,55 @@ impl ImpController {
 #[derive(Bundle)]
 pub struct ImpControllerBundle {
     imp_controller: ImpController,
-    body: RigidBody,
-    collider: Collider,
-    ground_caster: ShapeCaster,
-    locked_axes: LockedAxes,
     physics: CreaturePhysicsBundle,
 }
 
 impl ImpControllerBundle {
-    pub fn new(collider: Collider) -> Self {
-        // Create shape caster as a slightly smaller version of collider
-        let mut caster_shape = collider.clone();
-        caster_shape.set_scale(Vector::ONE * 0.99, 10);
-
+    pub fn new(collider: Collider, scale: Vector) -> Self {
         Self {
             imp_controller: ImpController::new(),
-            body: RigidBody::Dynamic,
-            collider,
-            ground_caster: ShapeCaster::new(caster_shape, Vector::ZERO, 0.0, Dir2::NEG_Y)
-                .with_max_distance(10.0),
-            locked_axes: LockedAxes::ROTATION_LOCKED,
-            physics: CreaturePhysicsBundle::new(MOVEMENT_DAMPING, MAX_SLOPE_ANGLE),
+            physics: CreaturePhysicsBundle::new(
+                collider,
+                scale,
+                IMP_MOVEMENT_DAMPING,
+                MAX_SLOPE_ANGLE,
+            ),
+        }
+    }
+}
+
+/// The hitbox of a melee swing, removed once the swing is over.
+#[derive(Component)]
+struct ImpSwing {
+    time_remaining: f32,
+}
+
+fn imp_swing(direction: f32, damage: f32) -> impl Bundle {
+    (
+        Name::new("Imp Swing"),
+        ImpSwing {
+            time_remaining: IMP_MELEE_SWING_DURATION,
+        },
+        hitbox_prefab(
+            Collider::circle(70.0),
+            enemy_hit_boxes(),
+            IMP_MELEE_SWING_DURATION,
+            damage,
+            // In the imp's local space, so this is scaled down along with the sprite.
+            Transform::from_xyz(direction * 110.0, 0.0, 0.0),
+        ),
+    )
+}
+
+fn tick_imp_swings(
+    mut commands: Commands,
+    time: Res<Time>,
+    swings: Query<(Entity, &mut ImpSwing)>,
+) {
+    for (entity, mut swing) in swings {
+        swing.time_remaining -= time.delta_secs().adjust_precision();
+        if swing.time_remaining < 0.0 {
+            commands.entity(entity).despawn();
         }
     }
 }
@@ -103,42 +161,56 @@ impl ImpControllerBundle {
 fn enemy_decision_making(
     mut commands: Commands,
     time: Res<Time>,
-    target: Query<&Transform, With<CharacterController>>,
-    mut imps: Query<(
-        Entity,
-        &mut ImpController,
-        &Transform,
-        &mut LinearVelocity,
-        Has<Grounded>,
-    )>,
+    mut tokens: AttackTokens,
+    difficulty: Res<Difficulty>,
+    tuning: Res<Tuning>,
+    target: Single<&Transform, With<Player>>,
+    mut imps: Query<
+        (
+            Entity,
+            &mut ImpController,
+            &Transform,
+            &mut LinearVelocity,
+            &mut Sprite,
+            Has<Grounded>,
+            Option<&Hasted>,
+        ),
+        (Without<Player>, Without<Materializing>),
+    >,
 ) {
-    for (entity, mut imp, pos, mut velocity, is_grounded) in imps {
+    let gravity = tuning.physics.gravity * IMP_GRAVITY_SCALE;
+    let jump_impulse = tuning.enemy.jump_impulse;
+    let imp_tuning = &tuning.enemy.imp;
+    for (entity, mut imp, pos, mut velocity, mut sprite, is_grounded, hasted) in &mut imps {
         let delta_time = time.delta_secs_f64().adjust_precision();
-        imp.jump_attack_cooldown -= delta_time;
-        imp.melee_attack_cooldown -= delta_time;
+        let haste = Hasted::factor(hasted);
+        let max_x_velocity = imp_tuning.max_x_velocity * haste;
+        let cooldown_speed = haste * difficulty.scaling().ai_speed;
+        imp.jump_attack_cooldown -= delta_time * cooldown_speed;
+        imp.melee_attack_cooldown -= delta_time * cooldown_speed;
         imp.expected_time_until_jump_hits -= delta_time;
-        let target_coords = target.single().unwrap().translation;
+        let target_coords = target.translation;
         let target_length = target_coords.x - pos.translation.x;
         let target_height = target_coords.y - pos.translation.y;
+        sprite.flip_x = target_length < 0.0;
 
         //good time for a jump attack?
         if is_grounded
             && imp.jump_attack_cooldown <= 0.0
-            && (abs(target_height) >= USE_JUMP_ATTACK_HEIGHT_DIFF_THRESHOLD
-                || abs(target_length) >= USE_JUMP_ATTACK_MIN_LENGTH_THRESHOLD)
-            && target_height <= 0.5 * JUMP_IMPULSE / GRAVITY_ACCELERATION
+            && (abs(target_height) >= imp_tuning.jump_attack_min_height_diff
+                || abs(target_length) >= imp_tuning.jump_attack_min_distance)
+            && target_height <= 0.5 * jump_impulse.powf(2.0) / gravity
         {
-            let time_til_target = (JUMP_IMPULSE
-                + sqrt(JUMP_IMPULSE.powf(2.0) - 2.0 * GRAVITY_ACCELERATION * target_height))
-                / GRAVITY_ACCELERATION;
-            let x_velocity_to_reach_target = target_length
-                / (1.0 / MOVEMENT_DAMPING
-                    - 1.0 / MOVEMENT_DAMPING * exp(-time_til_target * MOVEMENT_DAMPING));
+            let time_til_target = (jump_impulse
+                + sqrt(jump_impulse.powf(2.0) - 2.0 * gravity * target_height))
+                / gravity;
+            let x_velocity_to_reach_target = target_length * IMP_MOVEMENT_DAMPING
+                / (1.0 - exp(-time_til_target * IMP_MOVEMENT_DAMPING));
             //ATTACK!!!
-            if x_velocity_to_reach_target <= MAX_X_VELOCITY {
-                velocity.y += JUMP_IMPULSE;
+            if abs(x_velocity_to_reach_target) <= max_x_velocity && tokens.try_take() {
+                velocity.y += jump_impulse;
                 velocity.x = x_velocity_to_reach_target;
-                imp.jump_attack_cooldown = JUMP_ATTACK_COOLDOWN;
+                imp.jump_attack_cooldown = imp_tuning.jump_attack_cooldown;
                 imp.expected_time_until_jump_hits = time_til_target;
                 continue;
             }
@@ -146,29 +218,33 @@ fn enemy_decision_making(
 
         // good time to melee attack?
         if is_grounded
-            && abs(target_height) <= USE_MELEE_MAX_HEIGHT_DIFF
-            && abs(target_length) <= USE_MELEE_MAX_LENGTH_DIFF
+            && imp.melee_attack_cooldown <= 0.0
+            && abs(target_height) <= imp_tuning.melee_max_height_diff
+            && abs(target_length) <= imp_tuning.melee_max_distance
+            && tokens.try_take()
         {
-            imp.melee_attack_cooldown = MELEE_ATTACK_COOLDOWN;
+            imp.melee_attack_cooldown = imp_tuning.melee_attack_cooldown;
+            commands
+                .entity(entity)
+                .with_child(imp_swing(target_length.signum(), imp_tuning.melee_damage));
             continue;
         }
 
         // just run at them lmao
         if imp.expected_time_until_jump_hits < 0.0
-            && (abs(target_height) > USE_MELEE_MAX_HEIGHT_DIFF
-                || abs(target_length) > STOP_RUNNING_DISTANCE)
+            && (abs(target_height) > imp_tuning.melee_max_height_diff
+                || abs(target_length) > IMP_STOP_RUNNING_DISTANCE)
         {
-            velocity.x += target_length.signum() * MOVEMENT_ACCELERATION * delta_time;
+            velocity.x = (velocity.x
+                + target_length.signum() * IMP_MOVEMENT_ACCELERATION * delta_time)
+                .clamp(-max_x_velocity, max_x_velocity);
             continue;
         }
 
         //in range so stop
-        if imp.expected_time_until_jump_hits < 0.0
-            && (abs(target_height) <= USE_MELEE_MAX_HEIGHT_DIFF
-                || abs(target_length) <= STOP_RUNNING_DISTANCE)
-        {
-            velocity.x +=
-                -velocity.x.signum() * (MOVEMENT_ACCELERATION * delta_time).max(abs(velocity.x));
+        if imp.expected_time_until_jump_hits < 0.0 {
+            velocity.x += -velocity.x.signum()
+                * (IMP_MOVEMENT_ACCELERATION * delta_time).min(abs(velocity.x));
             continue;
         }
     }
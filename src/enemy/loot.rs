@@ -0,0 +1,219 @@
+//! Pickups enemies drop when they die.
+//!
+//! Every enemy with a [`LootTable`] rolls each of its [`LootDrop`]s on death. Drops pop out of
+//! the enemy, get pulled in once the player is close and disappear if nobody picks them up.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    PausableSystems,
+    collision_layers::GameLayer,
+    enemy::{configs::*, slime::kill_everything_that_dies},
+    health::{ChangeHpEvent, DeathEvent},
+    player::{
+        attack::behaviour::{Attack, AttackPhase},
+        character::Player,
+    },
+    rng::GameRng,
+    screens::Screen,
+    tuning::Tuning,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LootAssets>();
+    app.add_systems(
+        Update,
+        (attract_loot, pick_up_loot, expire_loot)
+            .chain()
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Last,
+        drop_loot
+            .before(kill_everything_that_dies)
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Loot {
+    /// Heals the player by [`HEALTH_ORB_HEAL`].
+    HealthOrb,
+    /// Gives the player's attack a bit of fury, and more time to keep the chain going.
+    FuryShard,
+}
+
+/// One possible drop, rolled on its own.
+#[derive(Clone, Copy, Debug)]
+pub struct LootDrop {
+    pub loot: Loot,
+    /// Between 0 and 1.
+    pub chance: f32,
+}
+
+/// What an enemy can drop when it dies.
+#[derive(Component)]
+pub struct LootTable(pub &'static [LootDrop]);
+
+/// Seconds until a drop disappears.
+#[derive(Component)]
+struct LootLifetime(f32);
+
+const LOOT_RADIUS: f32 = 14.0;
+/// Blinks per second, once a drop is about to disappear.
+const LOOT_BLINK_RATE: f32 = 6.0;
+
+#[derive(Resource)]
+struct LootAssets {
+    mesh: Handle<Mesh>,
+    health_orb: Handle<ColorMaterial>,
+    fury_shard: Handle<ColorMaterial>,
+}
+
+impl FromWorld for LootAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Circle::new(LOOT_RADIUS));
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        Self {
+            mesh,
+            health_orb: materials.add(Color::srgb(0.2, 0.95, 0.35)),
+            fury_shard: materials.add(Color::srgb(1.0, 0.2, 0.15)),
+        }
+    }
+}
+
+fn loot(loot_assets: &LootAssets, loot: Loot, translation: Vec3, velocity: Vec2) -> impl Bundle {
+    let material = match loot {
+        Loot::HealthOrb => loot_assets.health_orb.clone(),
+        Loot::FuryShard => loot_assets.fury_shard.clone(),
+    };
+    (
+        Name::new(format!("{loot:?}")),
+        loot,
+        LootLifetime(LOOT_LIFETIME),
+        Mesh2d(loot_assets.mesh.clone()),
+        MeshMaterial2d(material),
+        Transform::from_translation(translation),
+        RigidBody::Dynamic,
+        Collider::circle(LOOT_RADIUS),
+        // Only lands on the ground, everything else goes right through.
+        CollisionLayers::new(GameLayer::Default, GameLayer::Ground),
+        LockedAxes::ROTATION_LOCKED,
+        Restitution::new(0.4),
+        LinearVelocity(velocity),
+        StateScoped(Screen::Gameplay),
+    )
+}
+
+fn drop_loot(
+    mut commands: Commands,
+    mut death_reader: EventReader<DeathEvent>,
+    mut rng: ResMut<GameRng>,
+    loot_assets: Res<LootAssets>,
+    enemies: Query<(&LootTable, &GlobalTransform)>,
+) {
+    for DeathEvent(entity) in death_reader.read() {
+        let Ok((table, transform)) = enemies.get(*entity) else {
+            continue;
+        };
+        for drop in table.0 {
+            if !rng.loot.gen_bool(drop.chance.clamp(0.0, 1.0) as f64) {
+                continue;
+            }
+            let angle = rng.loot.gen_range(-0.5..0.5_f32);
+            let velocity = Vec2::from_angle(angle).rotate(Vec2::Y) * LOOT_POP_SPEED;
+            commands.spawn(loot(
+                &loot_assets,
+                drop.loot,
+                transform.translation().with_z(4.0),
+                velocity,
+            ));
+        }
+    }
+}
+
+/// Pulls drops close to the player towards them, through anything in the way.
+fn attract_loot(
+    mut commands: Commands,
+    player: Single<&Transform, With<Player>>,
+    mut drops: Query<
+        (
+            Entity,
+            &Transform,
+            &mut LinearVelocity,
+            Has<ColliderDisabled>,
+        ),
+        (With<Loot>, Without<Player>),
+    >,
+) {
+    let target = player.translation.truncate();
+    for (entity, transform, mut velocity, attracted) in &mut drops {
+        let offset = target - transform.translation.truncate();
+        if !attracted && offset.length() > LOOT_MAGNET_RADIUS {
+            continue;
+        }
+        if !attracted {
+            commands
+                .entity(entity)
+                .insert((ColliderDisabled, GravityScale(0.0)));
+        }
+        velocity.0 = offset.normalize_or_zero() * LOOT_MAGNET_SPEED;
+    }
+}
+
+fn pick_up_loot(
+    mut commands: Commands,
+    mut change_hp: EventWriter<ChangeHpEvent>,
+    player: Single<(Entity, &Transform, Option<&mut Attack>), With<Player>>,
+    drops: Query<(Entity, &Loot, &Transform), Without<Player>>,
+    tuning: Res<Tuning>,
+) {
+    let (player, player_transform, mut attack) = player.into_inner();
+    for (entity, loot, transform) in &drops {
+        let distance = transform
+            .translation
+            .truncate()
+            .distance(player_transform.translation.truncate());
+        if distance > LOOT_PICKUP_RADIUS {
+            continue;
+        }
+        match loot {
+            Loot::HealthOrb => {
+                change_hp.write(ChangeHpEvent::new(player, HEALTH_ORB_HEAL));
+            }
+            // Only does something while the weapon is chain reacting.
+            Loot::FuryShard => {
+                if let Some(attack) = attack.as_mut() {
+                    attack.update_fury(true, &tuning.attack);
+                    if let AttackPhase::Ready(timer) = &mut attack.phase {
+                        timer.reset();
+                    }
+                }
+            }
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+fn expire_loot(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut drops: Query<(Entity, &mut LootLifetime, &mut Visibility)>,
+) {
+    for (entity, mut lifetime, mut visibility) in &mut drops {
+        lifetime.0 -= time.delta_secs();
+        if lifetime.0 < 0.0 {
+            commands.entity(entity).despawn();
+        } else if lifetime.0 < LOOT_BLINK_TIME {
+            *visibility = if (lifetime.0 * LOOT_BLINK_RATE).fract() < 0.5 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
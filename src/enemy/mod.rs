@@ -1,11 +1,36 @@
+pub mod behaviour;
 pub mod boss;
+pub mod boss_attacks;
+pub mod caster;
 pub mod configs;
+pub mod crowd;
+pub mod elite;
 pub mod eye;
+pub mod hit_reaction;
+pub mod imp;
+pub mod loot;
+pub mod navigation;
 pub mod slime;
+pub mod stuck;
+pub mod wisp;
 
 // pub configs::;
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((slime::plugin, boss::plugin, eye::plugin));
+    app.add_plugins((
+        behaviour::plugin,
+        slime::plugin,
+        boss::plugin,
+        caster::plugin,
+        crowd::plugin,
+        elite::plugin,
+        eye::plugin,
+        hit_reaction::plugin,
+        imp::plugin,
+        loot::plugin,
+        navigation::plugin,
+        stuck::plugin,
+        wisp::plugin,
+    ));
 }
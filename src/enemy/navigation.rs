@@ -0,0 +1,299 @@
+//! A navigation graph of the level's platforms, for enemies that jump between them.
+//!
+//! Every ground collider becomes a [`NavPlatform`] along the top of its bounding box. Two
+//! platforms are linked when a standard jump can get from one to the other without hitting the
+//! underside of the one it's jumping onto. Enemies look up their next hop with
+//! [`NavGraph::next_hop`].
+//!
+//! In dev builds, press F3 to draw the graph.
+
+use std::collections::VecDeque;
+
+use avian2d::prelude::*;
+#[cfg(feature = "dev")]
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+use crate::{collision_layers::GameLayer, enemy::configs::*, tuning::Tuning};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<NavGraph>();
+    app.add_systems(Update, build_nav_graph);
+
+    #[cfg(feature = "dev")]
+    {
+        app.init_gizmo_group::<NavGizmos>();
+        app.add_systems(Startup, hide_nav_gizmos);
+        app.add_systems(
+            Update,
+            (
+                toggle_nav_gizmos.run_if(input_just_pressed(TOGGLE_KEY)),
+                draw_nav_graph,
+            ),
+        );
+    }
+}
+
+/// The walkable top of a ground collider.
+#[derive(Clone, Copy, Debug)]
+pub struct NavPlatform {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+}
+
+impl NavPlatform {
+    fn center(&self) -> f32 {
+        (self.left + self.right) / 2.0
+    }
+
+    /// Keeps `x` on the platform, at least [`NAV_EDGE_MARGIN`] from its edges.
+    pub fn clamp(&self, x: f32) -> f32 {
+        if self.right - self.left < 2.0 * NAV_EDGE_MARGIN {
+            return self.center();
+        }
+        x.clamp(self.left + NAV_EDGE_MARGIN, self.right - NAV_EDGE_MARGIN)
+    }
+}
+
+/// A jump from one platform to another.
+#[derive(Clone, Copy, Debug)]
+pub struct NavLink {
+    pub from: usize,
+    pub to: usize,
+    /// Where to jump from, on top of `from`.
+    pub takeoff: Vec2,
+    /// Where the jump lands, on top of `to`.
+    pub landing: Vec2,
+    /// The velocity to jump with, under normal gravity.
+    pub impulse: Vec2,
+    /// Seconds from takeoff to landing.
+    pub flight_time: f32,
+}
+
+#[derive(Resource, Default)]
+pub struct NavGraph {
+    pub platforms: Vec<NavPlatform>,
+    pub links: Vec<NavLink>,
+}
+
+impl NavGraph {
+    /// The highest platform under `position`, if it's above one at all.
+    pub fn platform_below(&self, position: Vec2) -> Option<usize> {
+        self.platforms
+            .iter()
+            .enumerate()
+            .filter(|(_, platform)| {
+                (platform.left..=platform.right).contains(&position.x) && platform.top <= position.y
+            })
+            .max_by(|(_, a), (_, b)| a.top.total_cmp(&b.top))
+            .map(|(index, _)| index)
+    }
+
+    /// The first jump on the way from `from` to `to`, taking as few jumps as possible and no
+    /// faster than `max_x_velocity`. `None` if they are on the same platform or there's no way.
+    pub fn next_hop(&self, from: Vec2, to: Vec2, max_x_velocity: f32) -> Option<&NavLink> {
+        let start = self.platform_below(from)?;
+        let goal = self.platform_below(to)?;
+        if start == goal {
+            return None;
+        }
+
+        // Breadth first, remembering the link each platform was first reached by.
+        let mut reached_by: Vec<Option<usize>> = vec![None; self.platforms.len()];
+        let mut queue = VecDeque::from([start]);
+        while let Some(platform) = queue.pop_front() {
+            if platform == goal {
+                break;
+            }
+            for (index, link) in self.links.iter().enumerate() {
+                if link.from == platform
+                    && link.to != start
+                    && reached_by[link.to].is_none()
+                    && link.impulse.x.abs() <= max_x_velocity
+                {
+                    reached_by[link.to] = Some(index);
+                    queue.push_back(link.to);
+                }
+            }
+        }
+
+        let mut link = &self.links[reached_by[goal]?];
+        while link.from != start {
+            link = &self.links[reached_by[link.from]?];
+        }
+        Some(link)
+    }
+
+    /// Whether there's any way from `from` to `to`, jumping no faster than `max_x_velocity`.
+    /// `None` if either isn't above a platform.
+    pub fn can_reach(&self, from: Vec2, to: Vec2, max_x_velocity: f32) -> Option<bool> {
+        let start = self.platform_below(from)?;
+        let goal = self.platform_below(to)?;
+        Some(start == goal || self.next_hop(from, to, max_x_velocity).is_some())
+    }
+}
+
+fn build_nav_graph(
+    mut graph: ResMut<NavGraph>,
+    tuning: Res<Tuning>,
+    added: Query<&CollisionLayers, Added<Collider>>,
+    colliders: Query<(&Collider, &CollisionLayers, &Transform)>,
+) {
+    let is_ground = |layers: &CollisionLayers| layers.memberships.has_all(GameLayer::Ground);
+    // Jumps depend on gravity and jump impulse, so retuning them needs a rebuild too.
+    let retuned = tuning.is_changed() && !graph.platforms.is_empty();
+    if !added.iter().any(is_ground) && !retuned {
+        return;
+    }
+    let jump = JumpPhysics {
+        gravity: tuning.physics.gravity,
+        impulse: tuning.enemy.jump_impulse,
+    };
+
+    let platforms: Vec<NavPlatform> = colliders
+        .iter()
+        .filter(|(_, layers, _)| is_ground(layers))
+        .map(|(collider, _, transform)| {
+            let aabb = collider.aabb(transform.translation.truncate(), transform.rotation);
+            NavPlatform {
+                left: aabb.min.x,
+                right: aabb.max.x,
+                top: aabb.max.y,
+            }
+        })
+        .collect();
+
+    let mut links = Vec::new();
+    for (from, a) in platforms.iter().enumerate() {
+        for (to, b) in platforms.iter().enumerate() {
+            if from == to {
+                continue;
+            }
+            if let Some(link) = jump_link(a, b, jump) {
+                links.push(NavLink { from, to, ..link });
+            }
+        }
+    }
+
+    info!(
+        "Built a navigation graph with {} platforms and {} links",
+        platforms.len(),
+        links.len()
+    );
+    *graph = NavGraph { platforms, links };
+}
+
+/// What the jumps in the graph are worked out with.
+#[derive(Clone, Copy)]
+struct JumpPhysics {
+    gravity: f32,
+    /// The highest vertical speed an enemy can jump with.
+    impulse: f32,
+}
+
+impl JumpPhysics {
+    /// The vertical speed to peak `height` above the takeoff point.
+    fn vertical_speed(self, height: f32) -> f32 {
+        (2.0 * self.gravity * height).sqrt()
+    }
+
+    /// Seconds until a jump going up at `vy` is `height` above its takeoff point, on the way up
+    /// and on the way down.
+    fn times_at_height(self, vy: f32, height: f32) -> (f32, f32) {
+        let root = (vy * vy - 2.0 * self.gravity * height).max(0.0).sqrt();
+        ((vy - root) / self.gravity, (vy + root) / self.gravity)
+    }
+}
+
+/// Works out a jump from the top of `a` to the top of `b`. `from` and `to` are left at zero.
+fn jump_link(a: &NavPlatform, b: &NavPlatform, jump: JumpPhysics) -> Option<NavLink> {
+    let direction = (b.center() - a.center()).signum();
+    if direction == 0.0 {
+        return None;
+    }
+    let height = b.top - a.top;
+    let vy = jump.vertical_speed(height.max(0.0) + NAV_APEX_CLEARANCE);
+    if vy > jump.impulse {
+        return None;
+    }
+    let (time_to_clear, flight_time) = jump.times_at_height(vy, height);
+
+    let (takeoff, landing) = if height > 0.0 {
+        // Jumping up: land just past the near edge of `b`, having taken off far enough before
+        // it to be above it by the time the body gets there.
+        let edge = if direction > 0.0 { b.left } else { b.right };
+        let landing = edge + direction * NAV_LANDING_MARGIN;
+        let ratio = time_to_clear / flight_time;
+        let run_up = (NAV_BODY_RADIUS + ratio * NAV_LANDING_MARGIN) / (1.0 - ratio);
+        let takeoff = a.clamp(edge - direction * run_up);
+        let clearance_time =
+            (edge - direction * NAV_BODY_RADIUS - takeoff) / (landing - takeoff) * flight_time;
+        // Leave some room for rounding, the run up is worked out to clear it exactly.
+        if clearance_time < time_to_clear - 0.01 {
+            return None;
+        }
+        (takeoff, landing)
+    } else {
+        // Jumping down or across: hop off the near edge of `a` and land past it on `b`.
+        let edge = if direction > 0.0 { a.right } else { a.left };
+        let takeoff = edge - direction * NAV_EDGE_MARGIN;
+        let near = if direction > 0.0 { b.left } else { b.right };
+        let landing = if direction > 0.0 {
+            (edge + NAV_LANDING_MARGIN).max(near + NAV_LANDING_MARGIN)
+        } else {
+            (edge - NAV_LANDING_MARGIN).min(near - NAV_LANDING_MARGIN)
+        };
+        if b.clamp(landing) != landing {
+            return None;
+        }
+        (takeoff, landing)
+    };
+
+    Some(NavLink {
+        from: 0,
+        to: 0,
+        takeoff: Vec2::new(takeoff, a.top),
+        landing: Vec2::new(landing, b.top),
+        impulse: Vec2::new((landing - takeoff) / flight_time, vy),
+        flight_time,
+    })
+}
+
+#[cfg(feature = "dev")]
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+
+#[cfg(feature = "dev")]
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct NavGizmos;
+
+#[cfg(feature = "dev")]
+fn hide_nav_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    config_store.config_mut::<NavGizmos>().0.enabled = false;
+}
+
+#[cfg(feature = "dev")]
+fn toggle_nav_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<NavGizmos>();
+    config.enabled = !config.enabled;
+}
+
+#[cfg(feature = "dev")]
+fn draw_nav_graph(graph: Res<NavGraph>, tuning: Res<Tuning>, mut gizmos: Gizmos<NavGizmos>) {
+    const ARC_STEPS: usize = 16;
+
+    for platform in &graph.platforms {
+        gizmos.line_2d(
+            Vec2::new(platform.left, platform.top),
+            Vec2::new(platform.right, platform.top),
+            Color::srgb(0.2, 0.9, 0.3),
+        );
+    }
+    for link in &graph.links {
+        let arc = (0..=ARC_STEPS).map(|step| {
+            let t = link.flight_time * step as f32 / ARC_STEPS as f32;
+            link.takeoff + link.impulse * t - Vec2::Y * tuning.physics.gravity * t * t / 2.0
+        });
+        gizmos.linestrip_2d(arc, Color::srgb(0.95, 0.8, 0.2));
+    }
+}
@@ -9,23 +9,44 @@ use crate::{
     PausableSystems,
     asset_tracking::LoadResource,
     collision_layers::{enemy_hit_boxes, enemy_hurt_boxes},
-    enemy::{boss::BossController, configs::*},
-    health::{DeathEvent, Health, health_bar, hitbox_prefab, hurtbox_prefab},
-    physics::{
-        configs::GRAVITY_ACCELERATION,
-        creature::{CreaturePhysicsBundle, Grounded},
+    enemy::{
+        behaviour::{AddStateMachine, EnemyAiSystems, EnemyState, SpriteTint, StateMachine},
+        boss::BossController,
+        caster::CasterController,
+        configs::*,
+        crowd::{AttackTokens, CrowdMember},
+        elite::Hasted,
+        imp::ImpController,
+        loot::LootTable,
+        navigation::NavGraph,
+        stuck::{StuckDetector, relocation_point},
+        wisp::WispController,
     },
+    health::{Armor, DeathEvent, Health, health_bar, hitbox_prefab, hurtbox_prefab},
+    physics::creature::{CreaturePhysicsBundle, Grounded},
     player::character::Player,
+    rng::GameRng,
+    script::spawning::Materializing,
+    tuning::{EnemyTuning, SlimeTuning, Tuning},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<SlimeAssets>();
+    app.add_state_machine::<SlimeState>();
     app.load_resource::<SlimeAssets>()
         .add_systems(
             Update,
-            (enemy_decision_making, slime_fall_recovery).in_set(PausableSystems),
+            (
+                enemy_decision_making.in_set(EnemyAiSystems::Act),
+                slime_fall_recovery.in_set(PausableSystems),
+            ),
         )
-        .add_systems(Last, kill_everything_that_dies.in_set(PausableSystems));
+        .add_systems(
+            Last,
+            (split_slimes, kill_everything_that_dies)
+                .chain()
+                .in_set(PausableSystems),
+        );
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -46,39 +67,79 @@ impl FromWorld for SlimeAssets {
     }
 }
 
-pub fn slime(slime_assets: &SlimeAssets, translation: Vec3, is_red: bool) -> impl Bundle {
-    let scale = Vec2::splat(0.5);
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SlimeKind {
+    Black,
+    Red,
+    /// Splits into two [`SlimeKind::Splitling`]s when it dies.
+    Splitting,
+    Splitling,
+    /// Jumps again as soon as it lands.
+    Bouncer,
+    /// Mostly shrugs off hits that aren't downward attacks from above.
+    Armored,
+}
+
+impl SlimeKind {
+    pub fn stats(self) -> &'static SlimeStats {
+        match self {
+            Self::Black => &BLACK_SLIME,
+            Self::Red => &RED_SLIME,
+            Self::Splitting => &SPLITTING_SLIME,
+            Self::Splitling => &SPLITLING_SLIME,
+            Self::Bouncer => &BOUNCER_SLIME,
+            Self::Armored => &ARMORED_SLIME,
+        }
+    }
+
+    pub fn tuning(self, tuning: &EnemyTuning) -> &SlimeTuning {
+        match self {
+            Self::Black => &tuning.black_slime,
+            Self::Red => &tuning.red_slime,
+            Self::Splitting => &tuning.splitting_slime,
+            Self::Splitling => &tuning.splitling_slime,
+            Self::Bouncer => &tuning.bouncer_slime,
+            Self::Armored => &tuning.armored_slime,
+        }
+    }
+}
+
+pub fn slime(
+    slime_assets: &SlimeAssets,
+    translation: Vec3,
+    kind: SlimeKind,
+    rng: &mut impl Rng,
+    tuning: &EnemyTuning,
+) -> impl Bundle {
+    let stats = kind.stats();
+    let slime_tuning = kind.tuning(tuning);
+    let scale = Vec2::splat(stats.scale);
     (
-        Name::new("Slime"),
+        Name::new(format!("{kind:?} Slime")),
         Transform::from_scale(scale.extend(1.0)).with_translation(translation),
         Sprite {
-            image: if is_red {
+            image: if kind == SlimeKind::Red {
                 slime_assets.slime1.clone()
             } else {
                 slime_assets.slime2.clone()
             },
             custom_size: Some(Vec2::new(4500.0 / 30., 3127.0 / 30.)),
+            color: stats.tint,
             ..default()
         },
-        SlimeControllerBundle::new(
-            Collider::circle(55.0),
-            scale,
-            if is_red {
-                RED_MAX_X_VELOCITY
-            } else {
-                BLACK_MAX_X_VELOCITY
-            },
-            if is_red {
-                RED_JUMP_ATTACK_COOLDOWN
-            } else {
-                BLACK_JUMP_ATTACK_COOLDOWN
-            },
-        ),
-        Health::new(if is_red { RED_HEALTH } else { BLACK_HEALTH }),
+        SpriteTint(stats.tint),
+        SlimeControllerBundle::new(Collider::circle(55.0), scale, kind, rng, slime_tuning),
+        Health::new(slime_tuning.health),
+        Armor {
+            damage_taken: stats.damage_taken,
+        },
         Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
         Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
         ColliderDensity(2.0),
         GravityScale(1.0),
+        StuckDetector::new(slime_tuning.max_x_velocity),
+        CrowdMember,
+        LootTable(stats.loot),
         children![
             hurtbox_prefab(
                 Collider::circle(60.0),
@@ -90,7 +151,7 @@ pub fn slime(slime_assets: &SlimeAssets, translation: Vec3, is_red: bool) -> imp
                 Collider::circle(60.0),
                 enemy_hit_boxes(),
                 0.0,
-                if is_red { 15.0 } else { 8.0 },
+                slime_tuning.damage,
                 Transform::default(),
             ),
             health_bar(Transform::from_xyz(-40., 120., 1.), Vec2::new(80.0, 5.0))
@@ -98,22 +159,35 @@ pub fn slime(slime_assets: &SlimeAssets, translation: Vec3, is_red: bool) -> imp
     )
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SlimeState {
+    /// Sitting still after landing.
+    Rest,
+    /// Waiting to be on the ground to jump.
+    Ready,
+    /// About to jump at the player.
+    Windup,
+    /// In the middle of a jump attack.
+    Airborne,
+}
+
+impl EnemyState for SlimeState {
+    fn is_telegraph(&self) -> bool {
+        matches!(self, Self::Windup)
+    }
+}
+
 #[derive(Component)]
 pub struct SlimeController {
-    max_x_velocity: f32,
-    jump_attack_full_cooldown: f32,
-    jump_attack_cooldown: f32,
-    expected_time_until_jump_hits: f32,
+    kind: SlimeKind,
 }
 impl SlimeController {
-    fn new(max_x_velocity: f32, jump_attack_full_cooldown: f32) -> Self {
-        let roll: f32 = rand::thread_rng().gen_range(0.75..(jump_attack_full_cooldown / 2.));
-        Self {
-            max_x_velocity,
-            jump_attack_full_cooldown,
-            jump_attack_cooldown: roll,
-            expected_time_until_jump_hits: 0.0,
-        }
+    fn new(kind: SlimeKind) -> Self {
+        Self { kind }
+    }
+
+    pub fn kind(&self) -> SlimeKind {
+        self.kind
     }
 }
 
@@ -122,6 +196,7 @@ impl SlimeController {
 #[derive(Bundle)]
 pub struct SlimeControllerBundle {
     slime_controller: SlimeController,
+    state_machine: StateMachine<SlimeState>,
     physics: CreaturePhysicsBundle,
 }
 
@@ -129,61 +204,149 @@ impl SlimeControllerBundle {
     pub fn new(
         collider: Collider,
         scale: Vector,
-        max_x_velocity: f32,
-        jump_attack_full_cooldown: f32,
+        kind: SlimeKind,
+        rng: &mut impl Rng,
+        tuning: &SlimeTuning,
     ) -> Self {
+        // Stagger the first jump so slimes spawned together don't move in lockstep.
+        let cooldown = tuning.jump_attack_cooldown;
+        let roll: f32 = rng.gen_range(0.75..(cooldown / 2.).max(1.0));
         Self {
-            slime_controller: SlimeController::new(max_x_velocity, jump_attack_full_cooldown),
+            slime_controller: SlimeController::new(kind),
+            state_machine: StateMachine::new(SlimeState::Rest)
+                .with_timeout(roll, SlimeState::Ready),
             physics: CreaturePhysicsBundle::new(collider, scale, MOVEMENT_DAMPING, MAX_SLOPE_ANGLE),
         }
     }
 }
 
 fn enemy_decision_making(
-    mut commands: Commands,
-    time: Res<Time>,
     target: Single<&Transform, With<Player>>,
-    mut slimes: Query<(
-        Entity,
-        &mut SlimeController,
-        &Transform,
-        &mut LinearVelocity,
-        Has<Grounded>,
-    )>,
+    graph: Res<NavGraph>,
+    mut tokens: AttackTokens,
+    tuning: Res<Tuning>,
+    mut slimes: Query<
+        (
+            &SlimeController,
+            &mut StateMachine<SlimeState>,
+            &Transform,
+            &mut LinearVelocity,
+            Has<Grounded>,
+            Option<&Hasted>,
+        ),
+        Without<Materializing>,
+    >,
 ) {
-    for (entity, mut slime, pos, mut velocity, is_grounded) in slimes {
-        let delta_time = time.delta_secs_f64().adjust_precision();
-        slime.jump_attack_cooldown -= delta_time;
-        slime.expected_time_until_jump_hits -= delta_time;
-        let target_coords = target.translation;
-        let target_length = target_coords.x - pos.translation.x;
-        let target_height = (target_coords.y - pos.translation.y)
-            .min(0.5 * JUMP_IMPULSE.powf(2.0) / GRAVITY_ACCELERATION);
-
-        //good time for a jump attack?
-        if is_grounded && slime.jump_attack_cooldown <= 0.0 {
-            let time_til_target = (JUMP_IMPULSE
-                + sqrt(JUMP_IMPULSE.powf(2.0) - 2.0 * GRAVITY_ACCELERATION * target_height))
-                / GRAVITY_ACCELERATION;
-            //just assume no dampening
-            let x_velocity_to_reach_target =
-                (abs(target_length) / time_til_target).min(slime.max_x_velocity);
-            //ATTACK!!!
-            velocity.y += JUMP_IMPULSE;
-            velocity.x = target_length.signum() * x_velocity_to_reach_target;
-            slime.jump_attack_cooldown = slime.jump_attack_full_cooldown + time_til_target / 2.0;
+    let gravity = tuning.physics.gravity;
+    let jump_impulse = tuning.enemy.jump_impulse;
+    for (slime, mut machine, pos, mut velocity, is_grounded, hasted) in &mut slimes {
+        let slime_tuning = slime.kind.tuning(&tuning.enemy);
+        let max_x_velocity = slime_tuning.max_x_velocity * Hasted::factor(hasted);
+        if machine.just_entered() {
+            match machine.state() {
+                SlimeState::Rest => {
+                    machine.transition_after(slime_tuning.jump_attack_cooldown, SlimeState::Ready);
+                }
+                SlimeState::Windup => {
+                    machine.transition_after(SLIME_WINDUP_DURATION, SlimeState::Airborne);
+                }
+                //ATTACK!!!
+                SlimeState::Airborne if is_grounded => {
+                    let position = pos.translation.truncate();
+                    let player = target.translation.truncate();
+                    let hop = graph.next_hop(position, player, max_x_velocity);
+                    if let Some(link) = hop
+                        .filter(|link| (link.takeoff.x - position.x).abs() <= NAV_TAKEOFF_TOLERANCE)
+                    {
+                        velocity.0 = link.impulse;
+                        machine.transition_after(link.flight_time / 2.0, SlimeState::Rest);
+                        continue;
+                    }
+                    // Hop towards the next jump, or at the player if they are on the same
+                    // platform, without jumping off it.
+                    let aim = hop.map_or(player, |link| link.takeoff);
+                    let aim_x = graph
+                        .platform_below(position)
+                        .map_or(aim.x, |platform| graph.platforms[platform].clamp(aim.x));
+                    let target_length = aim_x - pos.translation.x;
+                    let target_height =
+                        (aim.y - pos.translation.y).min(0.5 * jump_impulse.powf(2.0) / gravity);
+                    let time_til_target = (jump_impulse
+                        + sqrt(jump_impulse.powf(2.0) - 2.0 * gravity * target_height))
+                        / gravity;
+                    //just assume no dampening
+                    let x_velocity_to_reach_target =
+                        (abs(target_length) / time_til_target).min(max_x_velocity);
+                    velocity.y += jump_impulse;
+                    velocity.x = target_length.signum() * x_velocity_to_reach_target;
+                    machine.transition_after(time_til_target / 2.0, SlimeState::Rest);
+                }
+                // Knocked off the ground while winding up, try again once landed.
+                SlimeState::Airborne => machine.transition(SlimeState::Ready),
+                SlimeState::Ready => {}
+            }
+        }
+
+        match machine.state() {
+            SlimeState::Ready if is_grounded => {
+                // Only hops at the player are attacks. Hops towards a jump just get the slime
+                // around the arena, so they don't need a token.
+                let position = pos.translation.truncate();
+                let player = target.translation.truncate();
+                let attacking = graph.next_hop(position, player, max_x_velocity).is_none();
+                if !attacking || tokens.try_take() {
+                    machine.transition(SlimeState::Windup);
+                }
+            }
+            SlimeState::Rest | SlimeState::Windup if is_grounded => velocity.x = 0.0,
+            _ => {}
+        }
+    }
+}
+
+fn split_slimes(
+    mut commands: Commands,
+    mut death_reader: EventReader<DeathEvent>,
+    slime_assets: Res<SlimeAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<Tuning>,
+    slimes: Query<(&SlimeController, &Transform)>,
+) {
+    for DeathEvent(entity) in death_reader.read() {
+        let Ok((controller, transform)) = slimes.get(*entity) else {
+            continue;
+        };
+        if controller.kind != SlimeKind::Splitting {
             continue;
         }
-        if is_grounded && slime.jump_attack_cooldown < slime.jump_attack_full_cooldown {
-            velocity.x = 0.0;
+        for side in [-1.0, 1.0] {
+            let translation = transform.translation + Vec3::X * side * SPLIT_OFFSET_X;
+            commands
+                .spawn(slime(
+                    &slime_assets,
+                    translation,
+                    SlimeKind::Splitling,
+                    &mut rng.ai,
+                    &tuning.enemy,
+                ))
+                .insert(LinearVelocity(SPLIT_VELOCITY * Vec2::new(side, 1.0)));
         }
     }
 }
 
-fn kill_everything_that_dies(
+pub(super) fn kill_everything_that_dies(
     mut commands: Commands,
     mut death_reader: EventReader<DeathEvent>,
-    slimes: Query<Entity, Or<(With<SlimeController>, With<BossController>)>>,
+    slimes: Query<
+        Entity,
+        Or<(
+            With<SlimeController>,
+            With<BossController>,
+            With<ImpController>,
+            With<WispController>,
+            With<CasterController>,
+        )>,
+    >,
 ) {
     for DeathEvent(entity) in death_reader.read() {
         if slimes.contains(*entity) {
@@ -193,14 +356,26 @@ fn kill_everything_that_dies(
 }
 
 fn slime_fall_recovery(
-    mut slimes: Query<(&mut Transform, &mut LinearVelocity), With<SlimeController>>,
+    player: Single<&Transform, With<Player>>,
+    mut slimes: Query<
+        (&mut Transform, &mut LinearVelocity),
+        (
+            Or<(
+                With<SlimeController>,
+                With<ImpController>,
+                With<CasterController>,
+            )>,
+            Without<Player>,
+        ),
+    >,
 ) {
     for mut slime in slimes {
         if slime.0.translation.y < -1500.0 {
-            slime.1.y = 0.0;
+            slime.1.0 = Vec2::ZERO;
             // TODO: add a period of invulnerability
-            slime.0.translation.y = 300.0;
-            slime.0.translation.x = 300.0;
+            let target = relocation_point(player.translation.truncate());
+            slime.0.translation.x = target.x;
+            slime.0.translation.y = target.y;
         }
     }
 }
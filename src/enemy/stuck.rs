@@ -0,0 +1,98 @@
+//! Puts enemies back into play when they get stuck somewhere the player can't reach.
+//!
+//! An enemy that has had no way to reach the player, according to the [`NavGraph`], and hasn't
+//! been hurt for [`STUCK_RELOCATE_SECONDS`] is teleported to whichever of the
+//! [`RELOCATION_SPAWNS`] is furthest from the player. Moving around doesn't count, so an enemy
+//! pacing back and forth on a ledge it can't get off still gets relocated.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    enemy::{
+        configs::{RELOCATION_SPAWNS, STUCK_RELOCATE_SECONDS},
+        navigation::NavGraph,
+    },
+    health::Health,
+    player::character::Player,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, relocate_stuck_enemies.in_set(PausableSystems));
+}
+
+#[derive(Component)]
+pub struct StuckDetector {
+    /// How fast the enemy can jump sideways, which decides what platforms it can get to.
+    max_x_velocity: f32,
+    /// Seconds since the enemy last had a way to the player or got hurt.
+    unreachable_time: f32,
+}
+
+impl StuckDetector {
+    pub fn new(max_x_velocity: f32) -> Self {
+        Self {
+            max_x_velocity,
+            unreachable_time: 0.0,
+        }
+    }
+}
+
+/// The relocation spawn furthest from the player, so a relocated enemy doesn't land on them.
+pub fn relocation_point(player: Vec2) -> Vec2 {
+    RELOCATION_SPAWNS
+        .into_iter()
+        .max_by(|a, b| {
+            a.distance_squared(player)
+                .total_cmp(&b.distance_squared(player))
+        })
+        .unwrap_or_default()
+}
+
+fn relocate_stuck_enemies(
+    time: Res<Time>,
+    graph: Res<NavGraph>,
+    player: Single<&Transform, With<Player>>,
+    mut enemies: Query<
+        (
+            &Name,
+            &mut StuckDetector,
+            &mut Transform,
+            &mut LinearVelocity,
+            Ref<Health>,
+        ),
+        Without<Player>,
+    >,
+) {
+    let player = player.translation.truncate();
+    // Don't judge while the player is over the void.
+    if graph.platform_below(player).is_none() {
+        return;
+    }
+    for (name, mut detector, mut transform, mut velocity, health) in &mut enemies {
+        let position = transform.translation.truncate();
+        // An enemy that isn't above any platform can't reach anything either.
+        let reachable = graph
+            .can_reach(position, player, detector.max_x_velocity)
+            .unwrap_or(false);
+        if health.is_changed() || reachable {
+            detector.unreachable_time = 0.0;
+            continue;
+        }
+
+        detector.unreachable_time += time.delta_secs();
+        if detector.unreachable_time < STUCK_RELOCATE_SECONDS {
+            continue;
+        }
+
+        let target = relocation_point(player);
+        warn!(
+            "{name} stuck at {position} for {:.0}s, relocating to {target}",
+            detector.unreachable_time
+        );
+        transform.translation = target.extend(transform.translation.z);
+        velocity.0 = Vec2::ZERO;
+        detector.unreachable_time = 0.0;
+    }
+}
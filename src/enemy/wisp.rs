@@ -0,0 +1,216 @@
+//! A flying enemy that hovers around the player and swoops in to attack.
+//!
+//! Wisps steer towards where they want to be instead of setting their velocity, so a group of
+//! them can also steer away from each other.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    asset_tracking::LoadResource,
+    collision_layers::{enemy_hit_boxes, enemy_hurt_boxes},
+    enemy::{
+        behaviour::{AddStateMachine, EnemyAiSystems, EnemyState, StateMachine},
+        configs::*,
+        crowd::AttackTokens,
+        elite::Hasted,
+        loot::LootTable,
+    },
+    health::{Health, ImmuneToDownwardAttacks, health_bar, hitbox_prefab, hurtbox_prefab},
+    physics::creature::Flying,
+    player::character::Player,
+    rng::GameRng,
+    script::spawning::Materializing,
+    tuning::{EnemyTuning, Tuning},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<WispAssets>();
+    app.add_state_machine::<WispState>();
+    app.load_resource::<WispAssets>()
+        .add_systems(Update, enemy_decision_making.in_set(EnemyAiSystems::Act));
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct WispAssets {
+    #[dependency]
+    wisp: Handle<Image>,
+}
+
+impl FromWorld for WispAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            wisp: assets.load("images/wisp.png"),
+        }
+    }
+}
+
+pub fn wisp(
+    wisp_assets: &WispAssets,
+    translation: Vec3,
+    rng: &mut impl Rng,
+    tuning: &EnemyTuning,
+) -> impl Bundle {
+    (
+        Name::new("Wisp"),
+        Transform::from_scale(Vec3::splat(WISP_SCALE)).with_translation(translation),
+        Sprite {
+            image: wisp_assets.wisp.clone(),
+            ..default()
+        },
+        WispController,
+        StateMachine::new(WispState::Hover)
+            .with_timeout(rng.gen_range(WISP_HOVER_TIME), WispState::Windup),
+        // Wisps fly through the level, so they don't need to collide with anything.
+        RigidBody::Kinematic,
+        Collider::circle(40.0),
+        CollisionLayers::NONE,
+        Flying,
+        Health::new(tuning.wisp.health),
+        ImmuneToDownwardAttacks,
+        LootTable(WISP_LOOT),
+        children![
+            hurtbox_prefab(
+                Collider::circle(45.0),
+                enemy_hurt_boxes(),
+                0.5,
+                Transform::default()
+            ),
+            hitbox_prefab(
+                Collider::circle(35.0),
+                enemy_hit_boxes(),
+                0.0,
+                tuning.wisp.contact_damage,
+                Transform::default(),
+            ),
+            health_bar(Transform::from_xyz(-40., 90., 1.), Vec2::new(80.0, 5.0))
+        ],
+    )
+}
+
+#[derive(Component)]
+pub struct WispController;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WispState {
+    /// Keeping its distance from the player.
+    Hover,
+    /// Stopping in the air before a swoop.
+    Windup,
+    /// Flying straight through where the player was.
+    Swoop,
+}
+
+impl EnemyState for WispState {
+    fn is_telegraph(&self) -> bool {
+        matches!(self, Self::Windup)
+    }
+}
+
+/// Changes `velocity` towards `desired`, by no more than `max_acceleration` per second.
+fn steer(velocity: &mut Vec2, desired: Vec2, max_acceleration: f32, delta_time: f32) {
+    *velocity += (desired - *velocity).clamp_length_max(max_acceleration * delta_time);
+}
+
+/// Velocity that moves towards `target` at `max_speed`, slowing down when close.
+fn arrive(position: Vec2, target: Vec2, max_speed: f32) -> Vec2 {
+    let offset = target - position;
+    let speed = max_speed * (offset.length() / WISP_ARRIVE_RADIUS).min(1.0);
+    offset.normalize_or_zero() * speed
+}
+
+/// Velocity away from every other wisp within [`WISP_SEPARATION_RADIUS`], stronger the closer
+/// they are.
+fn separation(entity: Entity, position: Vec2, wisps: &[(Entity, Vec2)], max_speed: f32) -> Vec2 {
+    let push: Vec2 = wisps
+        .iter()
+        .filter(|(other, _)| *other != entity)
+        .map(|(_, other)| position - *other)
+        .filter(|away| away.length() < WISP_SEPARATION_RADIUS)
+        .map(|away| away.normalize_or_zero() * (1.0 - away.length() / WISP_SEPARATION_RADIUS))
+        .sum();
+    push * max_speed * WISP_SEPARATION_WEIGHT
+}
+
+fn enemy_decision_making(
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    mut tokens: AttackTokens,
+    tuning: Res<Tuning>,
+    target: Single<&Transform, With<Player>>,
+    mut wisps: Query<
+        (
+            Entity,
+            &mut StateMachine<WispState>,
+            &Transform,
+            &mut LinearVelocity,
+            &mut Sprite,
+            Option<&Hasted>,
+        ),
+        (
+            With<WispController>,
+            Without<Player>,
+            Without<Materializing>,
+        ),
+    >,
+) {
+    let delta_time = time.delta_secs();
+    let wisp_tuning = &tuning.enemy.wisp;
+    let target = target.translation.truncate();
+    let positions: Vec<(Entity, Vec2)> = wisps
+        .iter()
+        .map(|(entity, _, transform, ..)| (entity, transform.translation.truncate()))
+        .collect();
+
+    for (entity, mut machine, transform, mut velocity, mut sprite, hasted) in &mut wisps {
+        let haste = Hasted::factor(hasted);
+        let position = transform.translation.truncate();
+        if machine.just_entered() {
+            match machine.state() {
+                WispState::Hover => {
+                    let hover_time = rng.ai.gen_range(WISP_HOVER_TIME);
+                    machine.transition_after(hover_time, WispState::Windup);
+                }
+                // Wait for the next chance if too many others are attacking.
+                WispState::Windup if !tokens.try_take() => {
+                    machine.transition(WispState::Hover);
+                }
+                WispState::Windup => {
+                    machine.transition_after(WISP_WINDUP_DURATION, WispState::Swoop);
+                }
+                WispState::Swoop => {
+                    velocity.0 =
+                        (target - position).normalize_or_zero() * wisp_tuning.swoop_speed * haste;
+                    machine.transition_after(WISP_SWOOP_DURATION, WispState::Hover);
+                }
+            }
+        }
+
+        let desired = match machine.state() {
+            WispState::Hover => {
+                // Stay above the player, on whichever side the wisp is already on.
+                let away = (position - target).normalize_or(Vec2::Y);
+                let away = away.with_y(away.y.max(0.5)).normalize();
+                let destination = target + away * WISP_PREFERRED_DISTANCE;
+                arrive(position, destination, wisp_tuning.max_speed) * haste
+            }
+            WispState::Windup => Vec2::ZERO,
+            // Keep going in a straight line, so the swoop can be dodged.
+            WispState::Swoop => velocity.0,
+        };
+        let desired = desired + separation(entity, position, &positions, wisp_tuning.max_speed);
+        steer(
+            &mut velocity.0,
+            desired,
+            WISP_MAX_ACCELERATION * haste,
+            delta_time,
+        );
+
+        if velocity.x != 0.0 {
+            sprite.flip_x = velocity.x < 0.0;
+        }
+    }
+}
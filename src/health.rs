@@ -8,13 +8,15 @@ use bevy::{prelude::*, sprite::Anchor};
 
 use crate::{
     PausableSystems,
-    player::{attack::systems::WowTheWeaponHit, weapon::WeaponHitbox},
+    difficulty::Difficulty,
+    player::{attack::systems::WowTheWeaponHit, character::Player, weapon::WeaponHitbox},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Health>();
     app.add_event::<DeathEvent>()
         .add_event::<ChangeHpEvent>()
+        .add_event::<HitLanded>()
         .add_systems(
             Update,
             (
@@ -38,6 +40,32 @@ impl Health {
     }
 }
 
+/// Entities with this don't lose health.
+#[derive(Component)]
+pub struct Invulnerable;
+
+/// Entities with this only take `damage_taken` of every hit, unless the hitbox has
+/// [`DownwardAttack`] and comes from above.
+#[derive(Component)]
+pub struct Armor {
+    pub damage_taken: f32,
+}
+
+/// Marks the player's weapon hitbox while it's attacking downwards.
+/// Deals full damage to [`Armor`] it hits from above.
+#[derive(Component)]
+pub struct DownwardAttack;
+
+/// Entities with this can't be hurt by a [`DownwardAttack`].
+#[derive(Component)]
+pub struct ImmuneToDownwardAttacks;
+
+/// Absorbs damage until `remaining` runs out, before any health is lost.
+#[derive(Component)]
+pub struct Shield {
+    pub remaining: f32,
+}
+
 #[derive(Event, Debug)]
 pub struct DeathEvent(pub Entity);
 
@@ -47,6 +75,24 @@ pub struct ChangeHpEvent {
     amount: f32,
 }
 
+impl ChangeHpEvent {
+    /// Negative amounts hurt, positive ones heal.
+    pub fn new(target: Entity, amount: f32) -> Self {
+        Self { target, amount }
+    }
+}
+
+/// Sent when a hitbox hurts something.
+#[derive(Event, Debug)]
+pub struct HitLanded {
+    /// The parent of the hitbox, or the hitbox itself if it has none.
+    pub attacker: Entity,
+    pub target: Entity,
+    pub damage: f32,
+    /// From the hitbox towards what it hit.
+    pub direction: Vec2,
+}
+
 pub fn hurtbox_prefab(
     collider: Collider,
     collision_layer: CollisionLayers,
@@ -101,6 +147,18 @@ pub struct HitBox {
     damage: f32,
 }
 
+impl HurtBox {
+    pub fn remaining_immunity_duration(&self) -> f32 {
+        self.remaining_immunity_duration
+    }
+}
+
+impl HitBox {
+    pub fn damage(&self) -> f32 {
+        self.damage
+    }
+}
+
 fn tick_hurt_boxes(query: Query<&mut HurtBox>, time: Res<Time>) {
     for mut hb in query {
         hb.remaining_immunity_duration -= time.delta_secs_f64().adjust_precision();
@@ -117,12 +175,17 @@ fn tick_hit_boxes(query: Query<&mut HitBox>, time: Res<Time>) {
 #[derive(Component)]
 pub struct HealthBar;
 
+/// The whole health bar, background included.
+#[derive(Component)]
+pub struct HealthBarFrame;
+
 pub fn health_bar(transform: Transform, size: Vec2) -> impl Bundle {
     let back_colour = Color::srgb(0.4, 0.4, 0.4);
     let front_colour = Color::srgb(1.0, 0., 0.);
 
     (
         Name::new("Health Bar"),
+        HealthBarFrame,
         transform,
         Visibility::default(),
         children![
@@ -166,27 +229,70 @@ fn update_health_bar(
 
 fn get_hurt(
     mut hurt_entities: Query<(Entity, &CollidingEntities, &mut HurtBox)>,
-    mut hitboxes: Query<(&mut HitBox, Has<WeaponHitbox>)>,
+    mut hitboxes: Query<(&mut HitBox, Has<WeaponHitbox>, Has<DownwardAttack>)>,
     mut hurt_event_writer: EventWriter<ChangeHpEvent>,
+    mut hit_landed_writer: EventWriter<HitLanded>,
     mut wow_the_weapon_hit: EventWriter<WowTheWeaponHit>,
     parent_query: Query<&ChildOf>,
+    armor: Query<&Armor>,
+    downward_immune: Query<(), With<ImmuneToDownwardAttacks>>,
+    transforms: Query<&GlobalTransform>,
+    players: Query<(), With<Player>>,
+    difficulty: Res<Difficulty>,
 ) {
+    let scaling = difficulty.scaling();
     for (hurt_entity, hurt_box_colliding_entities, mut hurt_box) in &mut hurt_entities {
         if hurt_box.remaining_immunity_duration > 0.0 {
             continue;
         }
         for hitbox_ent in hurt_box_colliding_entities.0.iter() {
             match (hitboxes.get_mut(*hitbox_ent), parent_query.get(hurt_entity)) {
-                (Ok((mut hitb, is_weapon_hitbox)), Ok(parent)) => {
+                (Ok((mut hitb, is_weapon_hitbox, is_downward_attack)), Ok(parent)) => {
                     if *hitb
                         .remaining_rehit_delays
                         .get(&hurt_entity)
                         .unwrap_or(&-1.)
                         <= 0.0
                     {
+                        if is_downward_attack && downward_immune.contains(parent.parent()) {
+                            continue;
+                        }
+                        let mut damage = hitb.damage;
+                        if let Ok(armor) = armor.get(parent.parent()) {
+                            let from_above =
+                                match (transforms.get(*hitbox_ent), transforms.get(hurt_entity)) {
+                                    (Ok(hitbox), Ok(hurtbox)) => {
+                                        hitbox.translation().y > hurtbox.translation().y
+                                    }
+                                    _ => false,
+                                };
+                            if !(is_downward_attack && from_above) {
+                                damage *= armor.damage_taken;
+                            }
+                        }
+                        let hurts_player = players.contains(parent.parent());
+                        if hurts_player {
+                            damage *= scaling.enemy_damage;
+                        }
                         hurt_event_writer.write(ChangeHpEvent {
                             target: parent.parent(),
-                            amount: -hitb.damage,
+                            amount: -damage,
+                        });
+                        let direction =
+                            match (transforms.get(*hitbox_ent), transforms.get(hurt_entity)) {
+                                (Ok(hitbox), Ok(hurtbox)) => (hurtbox.translation()
+                                    - hitbox.translation())
+                                .truncate()
+                                .normalize_or_zero(),
+                                _ => Vec2::ZERO,
+                            };
+                        hit_landed_writer.write(HitLanded {
+                            attacker: parent_query
+                                .get(*hitbox_ent)
+                                .map_or(*hitbox_ent, |attacker| attacker.parent()),
+                            target: parent.parent(),
+                            damage,
+                            direction,
                         });
 
                         if is_weapon_hitbox {
@@ -194,7 +300,11 @@ fn get_hurt(
                         }
                         let v = hurt_box.full_immunity_duration;
                         hitb.remaining_rehit_delays.insert(hurt_entity, v);
-                        hurt_box.remaining_immunity_duration = hurt_box.full_immunity_duration;
+                        hurt_box.remaining_immunity_duration = if hurts_player {
+                            hurt_box.full_immunity_duration * scaling.player_immunity
+                        } else {
+                            hurt_box.full_immunity_duration
+                        };
                         break;
                     }
                 }
@@ -207,7 +317,7 @@ fn get_hurt(
 fn change_hp(
     mut change_hp_reader: EventReader<ChangeHpEvent>,
     mut death_event_writer: EventWriter<DeathEvent>,
-    mut query: Query<&mut Health>,
+    mut query: Query<(&mut Health, Has<Invulnerable>, Option<&mut Shield>)>,
 ) {
     let mut accumulated_deltas: HashMap<Entity, f32> = HashMap::new();
 
@@ -215,8 +325,16 @@ fn change_hp(
         *accumulated_deltas.entry(event.target).or_insert(0.0) += event.amount;
     }
 
-    for (entity, delta) in accumulated_deltas {
-        if let Ok(mut health) = query.get_mut(entity) {
+    for (entity, mut delta) in accumulated_deltas {
+        if let Ok((mut health, is_invulnerable, shield)) = query.get_mut(entity) {
+            if is_invulnerable && delta < 0.0 {
+                continue;
+            }
+            if let Some(mut shield) = shield.filter(|_| delta < 0.0) {
+                let absorbed = shield.remaining.min(-delta);
+                shield.remaining -= absorbed;
+                delta += absorbed;
+            }
             health.current = (health.current + delta).min(health.max);
             if health.current <= 0.0 {
                 death_event_writer.write(DeathEvent(entity));
@@ -0,0 +1,257 @@
+//! In-game overlays: how many enemies are left, where the off-screen ones are and where
+//! incoming sky lasers will land.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    PausableSystems,
+    enemy::{
+        boss::{BossController, Lazer},
+        caster::CasterController,
+        imp::ImpController,
+        slime::{SlimeController, SlimeKind},
+        wisp::WispController,
+    },
+    projectile::Projectile,
+    screens::{Screen, title::TitleAssets},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<IndicatorAssets>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_enemy_counter);
+    app.add_systems(
+        Update,
+        (
+            update_enemy_counter,
+            (spawn_offscreen_indicators, update_offscreen_indicators).chain(),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Distance in logical pixels between an indicator and the edge of the screen.
+const INDICATOR_MARGIN: f32 = 30.0;
+const INDICATOR_SIZE: f32 = 20.0;
+/// In front of everything in the level.
+const INDICATOR_Z: f32 = 50.0;
+
+#[derive(Resource)]
+struct IndicatorAssets {
+    arrow: Handle<Mesh>,
+    warning: Handle<Mesh>,
+    black_slime: Handle<ColorMaterial>,
+    red_slime: Handle<ColorMaterial>,
+    splitting_slime: Handle<ColorMaterial>,
+    bouncer_slime: Handle<ColorMaterial>,
+    armored_slime: Handle<ColorMaterial>,
+    imp: Handle<ColorMaterial>,
+    wisp: Handle<ColorMaterial>,
+    caster: Handle<ColorMaterial>,
+    boss: Handle<ColorMaterial>,
+}
+
+impl FromWorld for IndicatorAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        // Points along +X, so it can be rotated straight to the target's direction.
+        let arrow = meshes.add(Triangle2d::new(
+            Vec2::new(INDICATOR_SIZE, 0.0),
+            Vec2::new(-INDICATOR_SIZE / 2.0, INDICATOR_SIZE / 2.0),
+            Vec2::new(-INDICATOR_SIZE / 2.0, -INDICATOR_SIZE / 2.0),
+        ));
+        let warning = meshes.add(Rhombus::new(INDICATOR_SIZE * 1.5, INDICATOR_SIZE * 2.0));
+        let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+        Self {
+            arrow,
+            warning,
+            black_slime: materials.add(Color::srgb(0.15, 0.15, 0.15)),
+            red_slime: materials.add(Color::srgb(0.9, 0.1, 0.1)),
+            splitting_slime: materials.add(Color::srgb(0.2, 0.75, 0.2)),
+            bouncer_slime: materials.add(Color::srgb(0.25, 0.45, 0.95)),
+            armored_slime: materials.add(Color::srgb(0.55, 0.55, 0.65)),
+            imp: materials.add(Color::srgb(0.95, 0.45, 0.1)),
+            wisp: materials.add(Color::srgb(0.6, 0.85, 1.0)),
+            caster: materials.add(Color::srgb(0.6, 0.3, 0.85)),
+            boss: materials.add(Color::srgb(0.902, 0.651, 0.082)),
+        }
+    }
+}
+
+#[derive(Component)]
+struct EnemyCounter;
+
+#[derive(Component)]
+struct OffscreenIndicator {
+    target: Entity,
+    /// Marks where a falling hazard will land instead of pointing at it.
+    landing: bool,
+}
+
+fn spawn_enemy_counter(mut commands: Commands, title_assets: Res<TitleAssets>) {
+    commands.spawn((
+        Name::new("Enemy Counter"),
+        EnemyCounter,
+        Text::default(),
+        TextFont {
+            font_size: 28.0,
+            font: title_assets.crimson.clone(),
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(20.0),
+            right: Px(30.0),
+            ..default()
+        },
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+fn update_enemy_counter(
+    counter: Single<(&mut Text, &mut Visibility), With<EnemyCounter>>,
+    enemies: Query<
+        (),
+        Or<(
+            With<SlimeController>,
+            With<BossController>,
+            With<ImpController>,
+            With<WispController>,
+            With<CasterController>,
+        )>,
+    >,
+) {
+    let (mut text, mut visibility) = counter.into_inner();
+    let remaining = enemies.iter().len();
+    *visibility = if remaining == 0 {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+    let label = format!("Enemies remaining: {remaining}");
+    if text.0 != label {
+        text.0 = label;
+    }
+}
+
+fn spawn_offscreen_indicators(
+    mut commands: Commands,
+    assets: Res<IndicatorAssets>,
+    enemies: Query<
+        (
+            Entity,
+            Option<&SlimeController>,
+            Has<ImpController>,
+            Has<WispController>,
+            Has<CasterController>,
+        ),
+        Or<(
+            Added<SlimeController>,
+            Added<BossController>,
+            Added<ImpController>,
+            Added<WispController>,
+            Added<CasterController>,
+        )>,
+    >,
+    sky_lazers: Query<Entity, (Added<Lazer>, With<Projectile>)>,
+) {
+    for (target, slime, is_imp, is_wisp, is_caster) in &enemies {
+        let color = match slime.map(SlimeController::kind) {
+            Some(SlimeKind::Black) => &assets.black_slime,
+            Some(SlimeKind::Red) => &assets.red_slime,
+            Some(SlimeKind::Splitting | SlimeKind::Splitling) => &assets.splitting_slime,
+            Some(SlimeKind::Bouncer) => &assets.bouncer_slime,
+            Some(SlimeKind::Armored) => &assets.armored_slime,
+            None if is_imp => &assets.imp,
+            None if is_wisp => &assets.wisp,
+            None if is_caster => &assets.caster,
+            None => &assets.boss,
+        };
+        commands.spawn(offscreen_indicator(
+            target,
+            false,
+            assets.arrow.clone(),
+            color.clone(),
+        ));
+    }
+    for target in &sky_lazers {
+        commands.spawn(offscreen_indicator(
+            target,
+            true,
+            assets.warning.clone(),
+            assets.boss.clone(),
+        ));
+    }
+}
+
+fn offscreen_indicator(
+    target: Entity,
+    landing: bool,
+    mesh: Handle<Mesh>,
+    color: Handle<ColorMaterial>,
+) -> impl Bundle {
+    (
+        Name::new("Offscreen Indicator"),
+        OffscreenIndicator { target, landing },
+        Mesh2d(mesh),
+        MeshMaterial2d(color),
+        Transform::default(),
+        Visibility::Hidden,
+        StateScoped(Screen::Gameplay),
+    )
+}
+
+/// Pins each indicator to the edge of the screen while its target is out of view.
+/// Enemy arrows point at the enemy, landing markers sit on the top edge above where the hazard falls.
+fn update_offscreen_indicators(
+    mut commands: Commands,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut indicators: Query<(Entity, &OffscreenIndicator, &mut Transform, &mut Visibility)>,
+    targets: Query<&GlobalTransform, Without<OffscreenIndicator>>,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    let Some(viewport) = camera.logical_viewport_rect() else {
+        return;
+    };
+    let inner = viewport.inflate(-INDICATOR_MARGIN);
+
+    for (entity, indicator, mut transform, mut visibility) in &mut indicators {
+        let Ok(target) = targets.get(indicator.target) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let target = target.translation();
+        let Ok(on_screen) = camera.world_to_viewport(camera_transform, target) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        // Viewport y grows downwards, so a hazard still above the screen has a smaller y.
+        let hidden = if indicator.landing {
+            on_screen.y >= viewport.min.y
+        } else {
+            viewport.contains(on_screen)
+        };
+        if hidden {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let edge = if indicator.landing {
+            Vec2::new(on_screen.x.clamp(inner.min.x, inner.max.x), inner.min.y)
+        } else {
+            on_screen.clamp(inner.min, inner.max)
+        };
+        let Ok(position) = camera.viewport_to_world_2d(camera_transform, edge) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        transform.translation = position.extend(INDICATOR_Z);
+        if !indicator.landing {
+            transform.rotation = Quat::from_rotation_z((target.truncate() - position).to_angle());
+        }
+        *visibility = Visibility::Inherited;
+    }
+}
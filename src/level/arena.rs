@@ -21,6 +21,7 @@ use crate::{
     },
     screens::{Screen, title::TitleAssets},
     script::script::dialogue,
+    tuning::Tuning,
 };
 
 pub fn plugin(app: &mut App) {
@@ -105,6 +106,7 @@ pub fn spawn_level(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     level_assets: Res<LevelAssets>,
+    tuning: Res<Tuning>,
 ) {
     commands.spawn((
         Name::new("Background"),
@@ -157,7 +159,8 @@ pub fn spawn_level(
                 &player_assets,
                 &player_layout_assets,
                 &mut meshes,
-                &mut materials
+                &mut materials,
+                &tuning.player,
             ),
             weapon(&weapon_assets)
         ],
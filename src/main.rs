@@ -10,23 +10,30 @@ mod camera;
 mod collision_layers;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod difficulty;
 mod enemy;
 mod health;
+mod hud;
 mod level;
 mod menus;
 mod physics;
 mod player;
+mod projectile;
+mod rng;
 mod screens;
 mod script;
+#[cfg(test)]
+mod tests;
 mod theme;
+mod tuning;
 
-use avian2d::{PhysicsPlugins, math::*, prelude::*};
+use avian2d::PhysicsPlugins;
+#[cfg(feature = "dev")]
+use avian2d::prelude::PhysicsDebugPlugin;
 use bevy::{asset::AssetMetaCheck, prelude::*};
 #[cfg(feature = "dev")]
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
-use crate::physics::configs::GRAVITY_ACCELERATION;
-
 pub const GAME_NAME: &'static str = "Vision of Asad";
 
 fn main() -> AppExit {
@@ -74,16 +81,21 @@ impl Plugin for AppPlugin {
             theme::plugin,
             health::plugin,
             player::plugin,
+            projectile::plugin,
             enemy::plugin,
             camera::plugin,
             animation::plugin,
             level::arena::plugin,
+            rng::plugin,
+        ));
+        app.add_plugins((
+            script::plugin,
+            hud::plugin,
+            difficulty::plugin,
+            tuning::plugin,
         ));
-        app.add_plugins((script::plugin,));
 
-        // pysicks
-        app.insert_resource(ClearColor(Color::srgb(0., 4. / 256., 73. / 256.)))
-            .insert_resource(Gravity(Vector::NEG_Y * GRAVITY_ACCELERATION));
+        app.insert_resource(ClearColor(Color::srgb(0., 4. / 256., 73. / 256.)));
 
         #[cfg(feature = "dev")]
         app.add_plugins((
@@ -93,26 +105,32 @@ impl Plugin for AppPlugin {
             WorldInspectorPlugin::new(),
         ));
 
-        // Order new `AppSystems` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (
-                AppSystems::TickTimers,
-                AppSystems::RecordInput,
-                AppSystems::Update,
-            )
-                .chain(),
-        );
-
-        // Set up the `Pause` state.
-        app.init_state::<Pause>();
-        app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+        configure_system_sets(app);
     }
 }
 
+/// Orders the app-wide system sets and sets up pausing.
+/// Also used by the headless test harness, which doesn't go through [`AppPlugin`].
+fn configure_system_sets(app: &mut App) {
+    // Order new `AppSystems` variants by adding them here:
+    app.configure_sets(
+        Update,
+        (
+            AppSystems::TickTimers,
+            AppSystems::RecordInput,
+            AppSystems::Update,
+        )
+            .chain(),
+    );
+
+    // Set up the `Pause` state.
+    app.init_state::<Pause>();
+    app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
+}
+
 /// High-level groupings of systems for the app in the `Update` schedule.
 /// When adding a new variant, make sure to order it in the `configure_sets`
-/// call above.
+/// call in [`configure_system_sets`].
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 enum AppSystems {
     /// Tick timers.
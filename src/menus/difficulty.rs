@@ -0,0 +1,62 @@
+//! The difficulty menu, opened when starting a run from the main menu.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    difficulty::Difficulty,
+    menus::Menu,
+    screens::{Screen, title::TitleAssets},
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Difficulty), spawn_difficulty_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Difficulty).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_difficulty_menu(mut commands: Commands, title_assets: Res<TitleAssets>) {
+    let [story, normal, hard, nightmare] = Difficulty::ALL;
+    commands.spawn((
+        widget::ui_root("Difficulty Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Difficulty),
+        children![
+            widget::title("Difficulty", &title_assets, 40.),
+            difficulty_button(story, &title_assets),
+            difficulty_button(normal, &title_assets),
+            difficulty_button(hard, &title_assets),
+            difficulty_button(nightmare, &title_assets),
+            widget::button("Back", go_back_on_click, &title_assets),
+        ],
+    ));
+}
+
+fn difficulty_button(difficulty: Difficulty, title_assets: &TitleAssets) -> impl Bundle {
+    widget::button(
+        difficulty.name(),
+        move |_: Trigger<Pointer<Click>>,
+              mut selected: ResMut<Difficulty>,
+              resource_handles: Res<ResourceHandles>,
+              mut next_screen: ResMut<NextState<Screen>>| {
+            *selected = difficulty;
+            if resource_handles.is_all_done() {
+                next_screen.set(Screen::Story);
+            } else {
+                next_screen.set(Screen::Loading);
+            }
+        },
+        title_assets,
+    )
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
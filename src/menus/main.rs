@@ -2,13 +2,7 @@
 
 use bevy::prelude::*;
 
-use crate::{
-    GAME_NAME,
-    asset_tracking::ResourceHandles,
-    menus::Menu,
-    screens::{Screen, title::TitleAssets},
-    theme::widget,
-};
+use crate::{GAME_NAME, menus::Menu, screens::title::TitleAssets, theme::widget};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -22,7 +16,7 @@ fn spawn_main_menu(mut commands: Commands, title_assets: Res<TitleAssets>) {
         #[cfg(not(target_family = "wasm"))]
         children![
             widget::title(GAME_NAME, &title_assets, 120.),
-            widget::button("Start", enter_loading_or_story_screen, &title_assets),
+            widget::button("Start", open_difficulty_menu, &title_assets),
             widget::button("Settings", open_settings_menu, &title_assets),
             widget::button("Credits", open_credits_menu, &title_assets),
             widget::button("Exit", exit_app, &title_assets),
@@ -30,23 +24,15 @@ fn spawn_main_menu(mut commands: Commands, title_assets: Res<TitleAssets>) {
         #[cfg(target_family = "wasm")]
         children![
             widget::title(GAME_NAME, &title_assets, 120.),
-            widget::button("Start", enter_loading_or_story_screen, &title_assets),
+            widget::button("Start", open_difficulty_menu, &title_assets),
             widget::button("Settings", open_settings_menu, &title_assets),
             widget::button("Credits", open_credits_menu, &title_assets),
         ],
     ));
 }
 
-fn enter_loading_or_story_screen(
-    _: Trigger<Pointer<Click>>,
-    resource_handles: Res<ResourceHandles>,
-    mut next_screen: ResMut<NextState<Screen>>,
-) {
-    if resource_handles.is_all_done() {
-        next_screen.set(Screen::Story);
-    } else {
-        next_screen.set(Screen::Loading);
-    }
+fn open_difficulty_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Difficulty);
 }
 
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
@@ -1,6 +1,7 @@
 //! The game's menus and transitions between them.
 
 mod credits;
+mod difficulty;
 mod main;
 mod pause;
 mod results;
@@ -13,6 +14,7 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_plugins((
         credits::plugin,
+        difficulty::plugin,
         main::plugin,
         settings::plugin,
         pause::plugin,
@@ -26,6 +28,7 @@ pub enum Menu {
     #[default]
     None,
     Main,
+    Difficulty,
     Credits,
     Settings,
     Pause,
@@ -3,6 +3,7 @@
 use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
 use crate::{
+    difficulty::Difficulty,
     health::Health,
     menus::Menu,
     player::character::Player,
@@ -22,6 +23,7 @@ fn spawn_credits_menu(
     mut commands: Commands,
     title_assets: Res<TitleAssets>,
     player_health: Single<&Health, With<Player>>,
+    difficulty: Res<Difficulty>,
 ) {
     let health = *player_health;
     commands.spawn((
@@ -35,7 +37,10 @@ fn spawn_credits_menu(
                 "Your final score is: (the higher the better)",
                 &title_assets
             ),
-            widget::label(health.current.to_string(), &title_assets),
+            widget::label(
+                format!("{} ({})", health.current, difficulty.name()),
+                &title_assets
+            ),
             widget::title("Thank you for playing!", &title_assets, 40.0),
             widget::button("Credits", go_credit_on_click, &title_assets),
         ],
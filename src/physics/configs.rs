@@ -1 +0,0 @@
-pub const GRAVITY_ACCELERATION: f32 = 1500.0;
@@ -28,7 +28,7 @@ pub struct Flying;
 /// The damping factor used for slowing down movement.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-pub struct MovementDampingFactor(Scalar);
+pub struct MovementDampingFactor(pub Scalar);
 /// The maximum angle a slope can have for a character controller
 /// to be able to climb and jump. If the slope is steeper than this angle,
 /// the character will slide down.
@@ -1,4 +1,3 @@
-pub mod configs;
 pub mod creature;
 
 use bevy::prelude::*;
@@ -2,24 +2,14 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 
+use crate::tuning::AttackTuning;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<InputAttackEvent>();
     app.add_event::<DoAttackEvent>();
     app.add_event::<AttackDirection>();
 }
 
-const INITIAL_ATTACK_COOLDOWN_SECONDS: f32 = 1.5;
-const MINIMUM_ATTACK_COOLDOWN_SECONDS: f32 = 0.05;
-const ATTACK_PERIOD_SECONDS: f32 = 2.;
-const GRACE_PERIOD_SECONDS: f32 = 2.0;
-const INITIAL_EXTEND_SCALE: f32 = 7.0;
-const MINIMUM_EXTEND_SCALE: f32 = 1.0;
-
-const SCALE_INCREASE_FACTOR: f32 = 1.2;
-const SCALE_DECREASE_FACTOR: f32 = 0.8;
-const COOLDOWN_INCREASE_FACTOR: f32 = 1.2;
-const COOLDOWN_DECREASE_FACTOR: f32 = 0.8;
-
 const WEAPON_ATTACK_HORIZONTAL_OFFSET: Vec3 = Vec3::new(-60.0, -47.0, -1.0);
 const WEAPON_ATTACK_VERTICAL_OFFSET: Vec3 = Vec3::new(30.0, 10.0, -1.0);
 
@@ -117,15 +107,6 @@ pub enum AttackPhase {
     Cooling(Timer),
 }
 
-impl Default for AttackPhase {
-    fn default() -> Self {
-        AttackPhase::Reacting(Timer::from_seconds(
-            INITIAL_ATTACK_COOLDOWN_SECONDS,
-            TimerMode::Once,
-        ))
-    }
-}
-
 impl AttackPhase {
     pub fn tick(&mut self, time: Duration) {
         match self {
@@ -145,43 +126,39 @@ impl AttackPhase {
             }
         };
     }
-    pub fn new_ready_timer() -> AttackPhase {
-        AttackPhase::Ready(Timer::from_seconds(ATTACK_PERIOD_SECONDS, TimerMode::Once))
+    pub fn new_ready_timer(tuning: &AttackTuning) -> AttackPhase {
+        AttackPhase::Ready(Timer::from_seconds(tuning.attack_period, TimerMode::Once))
     }
 
-    pub fn new_cooling_timer() -> AttackPhase {
-        AttackPhase::Cooling(Timer::from_seconds(GRACE_PERIOD_SECONDS, TimerMode::Once))
+    pub fn new_cooling_timer(tuning: &AttackTuning) -> AttackPhase {
+        AttackPhase::Cooling(Timer::from_seconds(tuning.grace_period, TimerMode::Once))
     }
 }
 
-impl Default for Attack {
-    fn default() -> Self {
-        Attack::new(INITIAL_ATTACK_COOLDOWN_SECONDS, INITIAL_EXTEND_SCALE)
-    }
-}
 impl Attack {
-    pub fn new(attack_delay: f32, extend_scale: f32) -> Self {
+    pub fn new(tuning: &AttackTuning) -> Self {
         Self {
-            phase: AttackPhase::default(),
-            attack_delay_seconds: attack_delay,
-            extend_scale,
+            phase: AttackPhase::Reacting(Timer::from_seconds(
+                tuning.initial_cooldown,
+                TimerMode::Once,
+            )),
+            attack_delay_seconds: tuning.initial_cooldown,
+            extend_scale: tuning.initial_extend_scale,
             position: AttackPosition::default(),
         }
     }
 
-    pub fn update_fury(&mut self, increase_fury: bool) {
-        // that u128 duration cast to f64 should be fine
-        // because it should never be bigger than INITIAL_ATTACK_COOLDOWN_MILLISECONDS
+    pub fn update_fury(&mut self, increase_fury: bool, tuning: &AttackTuning) {
         if increase_fury {
-            let decreased_cooldown = self.attack_delay_seconds * COOLDOWN_DECREASE_FACTOR;
-            self.attack_delay_seconds = decreased_cooldown.max(MINIMUM_ATTACK_COOLDOWN_SECONDS);
+            let decreased_cooldown = self.attack_delay_seconds * tuning.cooldown_decrease_factor;
+            self.attack_delay_seconds = decreased_cooldown.max(tuning.minimum_cooldown);
             self.extend_scale =
-                (self.extend_scale * SCALE_DECREASE_FACTOR).max(MINIMUM_EXTEND_SCALE);
+                (self.extend_scale * tuning.scale_decrease_factor).max(tuning.minimum_extend_scale);
         } else {
-            let increased_cooldown = self.attack_delay_seconds * COOLDOWN_INCREASE_FACTOR;
-            self.attack_delay_seconds = increased_cooldown.min(INITIAL_ATTACK_COOLDOWN_SECONDS);
+            let increased_cooldown = self.attack_delay_seconds * tuning.cooldown_increase_factor;
+            self.attack_delay_seconds = increased_cooldown.min(tuning.initial_cooldown);
             self.extend_scale =
-                (self.extend_scale * SCALE_INCREASE_FACTOR).min(INITIAL_EXTEND_SCALE);
+                (self.extend_scale * tuning.scale_increase_factor).min(tuning.initial_extend_scale);
         };
     }
 
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect};
+use crate::{asset_tracking::LoadResource, audio::sound_effect, rng::GameRng};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_event::<AttackSound>();
@@ -83,6 +83,7 @@ pub fn play_attack_sound(
     mut sound_event: EventReader<AttackSound>,
     mut commands: Commands,
     attack_assets: Res<AttackAssets>,
+    mut rng: ResMut<GameRng>,
 ) {
     for event in sound_event.read() {
         let sound = match event {
@@ -94,10 +95,11 @@ pub fn play_attack_sound(
                 let sound_index = wut_sound_to_play(*cooldown_second);
                 attack_assets.weapon_miss[sound_index].clone()
             }
-            AttackSound::Slash => {
-                let rng = &mut rand::thread_rng();
-                attack_assets.wind_slash.choose(rng).unwrap().clone()
-            }
+            AttackSound::Slash => attack_assets
+                .wind_slash
+                .choose(&mut rng.audio)
+                .unwrap()
+                .clone(),
         };
 
         commands.spawn(sound_effect(sound));
@@ -5,7 +5,7 @@ use bevy::prelude::*;
 
 use crate::{
     PausableSystems,
-    health::HitBox,
+    health::{DownwardAttack, HitBox},
     physics::creature::Grounded,
     player::{
         attack::{
@@ -16,6 +16,7 @@ use crate::{
         input::{gamepad_attack_input, keyboard_attack_input},
         weapon::{ItHitSomething, WeaponHitbox},
     },
+    tuning::Tuning,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -52,7 +53,9 @@ fn attack_handler(
     mut commands: Commands,
     time: Res<Time>,
     mut sound_event: EventWriter<AttackSound>,
+    tuning: Res<Tuning>,
 ) {
+    let tuning = &tuning.attack;
     let (fc_entity, mut fc_hb) = fuckin_cooliders.into_inner();
     // Loop so we consume events and don't block. but also we don't really care how many events get triggered
     let has_attack_input = input_event.read().fold(false, |acc, _| acc || true);
@@ -62,7 +65,7 @@ fn attack_handler(
         Some(a) => a,
         None => {
             if has_attack_input {
-                commands.entity(*entity).insert(Attack::default());
+                commands.entity(*entity).insert(Attack::new(tuning));
             }
             return;
         }
@@ -81,6 +84,12 @@ fn attack_handler(
                 sound_event.write(AttackSound::Slash);
                 fc_hb.remaining_rehit_delays = HashMap::new();
                 commands.entity(fc_entity).remove::<ColliderDisabled>();
+                // Only the downward attack gets through armor.
+                if player.attack_direction == Vec2::NEG_Y {
+                    commands.entity(fc_entity).insert(DownwardAttack);
+                } else {
+                    commands.entity(fc_entity).remove::<DownwardAttack>();
+                }
             }
         }
         // Attacking is handled by animation
@@ -92,9 +101,9 @@ fn attack_handler(
         AttackPhase::Ready(timer) => {
             commands.entity(fc_entity).insert(ColliderDisabled);
             if timer.just_finished() {
-                attack.update_fury(false);
+                attack.update_fury(false, tuning);
                 // if we are in ready phase, we can start cooling down
-                attack.phase = AttackPhase::new_cooling_timer();
+                attack.phase = AttackPhase::new_cooling_timer(tuning);
             } else if has_attack_input {
                 // this should go when you move to the ready phase
                 attack.phase = attack.new_reaction_timer();
@@ -116,7 +125,9 @@ fn do_attack(
     mut do_attack_event: EventReader<DoAttackEvent>,
     mut play_sound_writer: EventWriter<AttackSound>,
     mut commands: Commands,
+    tuning: Res<Tuning>,
 ) {
+    let tuning = &tuning.attack;
     let (mut attack, entity, it_hit_something) = player.into_inner();
 
     let is_attacking = matches!(attack.phase, AttackPhase::Attacking { .. });
@@ -124,13 +135,13 @@ fn do_attack(
     if is_attacking {
         for event in do_attack_event.read() {
             if event.in_attack_delay {
-                attack.update_fury(it_hit_something);
+                attack.update_fury(it_hit_something, tuning);
                 attack.position = attack.position.get_next();
 
                 if it_hit_something {
-                    attack.phase = AttackPhase::new_ready_timer();
+                    attack.phase = AttackPhase::new_ready_timer(tuning);
                 } else {
-                    attack.phase = AttackPhase::new_cooling_timer();
+                    attack.phase = AttackPhase::new_cooling_timer(tuning);
                 }
                 commands.entity(entity).remove::<ItHitSomething>();
             } else {
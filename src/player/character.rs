@@ -8,10 +8,9 @@ use crate::{
     health::{Health, health_bar, hurtbox_prefab},
     physics::creature::Grounded,
     player::movement::movement::{PlayerMovementBundle, PlayerMovementState},
+    tuning::{PlayerTuning, Tuning},
 };
 
-use super::configs::{CHARACTER_GRAVITY_SCALE, CHARACTER_HEALTH};
-
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<PlayerAssets>();
     app.register_type::<PlayerLayoutAssets>();
@@ -92,11 +91,12 @@ impl FromWorld for PlayerAssets {
 
 fn reset_player_gravity_scale(
     mut player: Single<(&mut GravityScale, Has<Grounded>), With<Player>>,
+    tuning: Res<Tuning>,
 ) {
     let (gs, is_grounded) = &mut *player;
 
     if *is_grounded {
-        gs.0 = CHARACTER_GRAVITY_SCALE;
+        gs.0 = tuning.player.gravity_scale;
     }
 }
 
@@ -119,6 +119,7 @@ pub fn player(
     player_layout_assets: &PlayerLayoutAssets,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<ColorMaterial>,
+    tuning: &PlayerTuning,
 ) -> impl Bundle {
     let movement_state = PlayerMovementState::Idle(false);
     let (image, texture_atlas) =
@@ -138,12 +139,12 @@ pub fn player(
             texture_atlas,
             ..default()
         },
-        PlayerMovementBundle::new(Collider::capsule(15.0, 170.0), Vector::ONE),
-        Health::new(CHARACTER_HEALTH),
+        PlayerMovementBundle::new(Collider::capsule(15.0, 170.0), Vector::ONE, tuning),
+        Health::new(tuning.health),
         Friction::ZERO.with_combine_rule(CoefficientCombine::Min),
         Restitution::ZERO.with_combine_rule(CoefficientCombine::Min),
         ColliderDensity(2.0),
-        GravityScale(CHARACTER_GRAVITY_SCALE),
+        GravityScale(tuning.gravity_scale),
         children![
             hurtbox_prefab(
                 Collider::capsule(30.0, 135.0),
@@ -1,17 +1,9 @@
 use avian2d::math::Scalar;
 use bevy::input::keyboard::KeyCode;
 
-pub const MOVEMENT_SPEED: f32 = 500.0;
-pub const DASH_SPEED_MODIFIER: f32 = 2.;
-pub const JUMP_IMPULSE: f32 = 1000.0;
-pub const MOVEMENT_DAMPING: f32 = 6.0;
-pub const MAX_SLOPE_ANGLE: f32 = (30.0 as Scalar).to_radians();
-pub const CHARACTER_GRAVITY_SCALE: f32 = 1.5;
-pub const DASH_DURATION: f32 = 0.216;
-pub const DASH_COOLDOWN_DURATION: f32 = 0.3;
-pub const JUMP_DURATION_SECONDS: f32 = 0.400;
+// Balance numbers for the player live in `assets/tuning.ron`, see `crate::tuning`.
 
-pub const CHARACTER_HEALTH: f32 = 100.0;
+pub const MAX_SLOPE_ANGLE: f32 = (30.0 as Scalar).to_radians();
 
 pub const KEYBOARD_LEFT: KeyCode = KeyCode::ArrowLeft;
 pub const KEYBOARD_RIGHT: KeyCode = KeyCode::ArrowRight;
@@ -1,6 +1,6 @@
 pub mod attack;
 pub mod character;
-mod configs;
+pub mod configs;
 pub mod input;
 pub mod movement;
 pub mod weapon;
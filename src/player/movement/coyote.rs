@@ -1,24 +1,25 @@
-use std::time::Duration;
-
 use bevy::prelude::*;
 
-use crate::{physics::creature::Grounded, player::character::Player};
+use crate::{physics::creature::Grounded, player::character::Player, tuning::Tuning};
 
 #[derive(Component)]
 #[component(storage = "SparseSet")]
 pub struct Coyote(Timer);
 
 impl Coyote {
-    fn new(duration: u64) -> Coyote {
-        Self(Timer::new(Duration::from_millis(duration), TimerMode::Once))
+    fn new(duration: f32) -> Coyote {
+        Self(Timer::from_seconds(duration, TimerMode::Once))
     }
 }
 
 pub fn detect_coyote_time_start(
     entity: Single<Entity, (With<Player>, With<Grounded>, Without<Coyote>)>,
     mut commands: Commands,
+    tuning: Res<Tuning>,
 ) {
-    commands.entity(*entity).insert(Coyote::new(200));
+    commands
+        .entity(*entity)
+        .insert(Coyote::new(tuning.player.coyote_time));
 }
 
 pub fn handle_coyote_time(
@@ -11,12 +11,10 @@ use crate::{
     physics::creature::{Flying, Grounded},
     player::{
         character::{Player, PlayerAssets},
-        configs::{
-            CHARACTER_GRAVITY_SCALE, DASH_COOLDOWN_DURATION, DASH_DURATION, DASH_SPEED_MODIFIER,
-            JUMP_DURATION_SECONDS, MOVEMENT_SPEED,
-        },
         movement::{movement::PlayerMovementState, movement_visual::SpriteImageChange},
     },
+    rng::GameRng,
+    tuning::Tuning,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -55,7 +53,10 @@ pub fn handle_dash_event(
     mut sprite_change_event: EventWriter<SpriteImageChange>,
     mut commands: Commands,
     player_assets: Res<PlayerAssets>,
+    mut rng: ResMut<GameRng>,
+    tuning: Res<Tuning>,
 ) {
+    let tuning = &tuning.player;
     let (
         entity,
         player,
@@ -77,34 +78,34 @@ pub fn handle_dash_event(
                     continue;
                 }
 
-                let rng = &mut rand::thread_rng();
                 let chosen = player_assets
                     .player_dash_sounds
-                    .choose(rng)
+                    .choose(&mut rng.audio)
                     .unwrap()
                     .clone();
                 commands.spawn(sound_effect(chosen));
                 commands.entity(entity).insert(Flying);
-                linear_velocity.x = player.face_direction.x * MOVEMENT_SPEED * DASH_SPEED_MODIFIER;
+                linear_velocity.x =
+                    player.face_direction.x * tuning.movement_speed * tuning.dash_speed_modifier;
                 linear_velocity.y = 0.0;
                 gravity.0 = 0.0;
                 commands.entity(entity).insert(DashingUsed);
-                *movement_state = PlayerMovementState::Dash(DASH_DURATION);
+                *movement_state = PlayerMovementState::Dash(tuning.dash_duration);
                 sprite_change_event.write(SpriteImageChange(movement_state.clone()));
             }
             false => {
                 commands.entity(entity).remove::<Flying>();
-                gravity.0 = CHARACTER_GRAVITY_SCALE;
+                gravity.0 = tuning.gravity_scale;
                 linear_velocity.x *= 0.4;
                 *movement_state = PlayerMovementState::Jump(Timer::from_seconds(
-                    JUMP_DURATION_SECONDS,
+                    tuning.jump_duration,
                     TimerMode::Once,
                 ));
                 sprite_change_event.write(SpriteImageChange(movement_state.clone()));
                 commands
                     .entity(entity)
                     .insert(DashingCooldown(Timer::from_seconds(
-                        DASH_COOLDOWN_DURATION,
+                        tuning.dash_cooldown,
                         TimerMode::Once,
                     )));
             }
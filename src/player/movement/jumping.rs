@@ -6,11 +6,11 @@ use crate::{
     physics::creature::Grounded,
     player::{
         character::Player,
-        configs::{CHARACTER_GRAVITY_SCALE, JUMP_DURATION_SECONDS, JUMP_IMPULSE},
         movement::{
             coyote::Coyote, movement::PlayerMovementState, movement_visual::SpriteImageChange,
         },
     },
+    tuning::Tuning,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -39,7 +39,9 @@ pub fn handle_jump_event(
     mut jump_event_reader: EventReader<JumpingEvent>,
     mut sprite_change_event: EventWriter<SpriteImageChange>,
     mut commands: Commands,
+    tuning: Res<Tuning>,
 ) {
+    let tuning = &tuning.player;
     let (entity, mut linear_velocity, mut gravity, mut movement_state, is_grounded, is_coyote) =
         player.into_inner();
 
@@ -54,17 +56,17 @@ pub fn handle_jump_event(
                     commands.entity(entity).remove::<Grounded>();
                     commands.entity(entity).remove::<Coyote>();
                     *movement_state = PlayerMovementState::Jump(Timer::from_seconds(
-                        JUMP_DURATION_SECONDS,
+                        tuning.jump_duration,
                         TimerMode::Once,
                     ));
                     sprite_change_event.write(SpriteImageChange(movement_state.clone()));
-                    linear_velocity.y += JUMP_IMPULSE;
-                    gravity.0 = 0.5;
+                    linear_velocity.y += tuning.jump_impulse;
+                    gravity.0 = tuning.jump_gravity_scale;
                 }
             }
             false => {
                 if !is_grounded && linear_velocity.y > 0.0 {
-                    gravity.0 = CHARACTER_GRAVITY_SCALE;
+                    gravity.0 = tuning.gravity_scale;
                     linear_velocity.y *= 0.5;
                 }
             }
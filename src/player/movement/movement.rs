@@ -7,10 +7,10 @@ use bevy::prelude::*;
 
 use crate::{
     PausableSystems,
-    physics::creature::CreaturePhysicsBundle,
+    physics::creature::{CreaturePhysicsBundle, MovementDampingFactor},
     player::{
         character::Player,
-        configs::{MAX_SLOPE_ANGLE, MOVEMENT_DAMPING, MOVEMENT_SPEED},
+        configs::MAX_SLOPE_ANGLE,
         input::{gamepad_movement_input, keyboard_movement_input},
         movement::{
             coyote::{detect_coyote_time_start, handle_coyote_time},
@@ -19,6 +19,7 @@ use crate::{
             movement_visual::SpriteImageChange,
         },
     },
+    tuning::{PlayerTuning, Tuning},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -36,6 +37,10 @@ pub(super) fn plugin(app: &mut App) {
             .chain()
             .in_set(PausableSystems),),
     );
+    app.add_systems(
+        Update,
+        apply_movement_damping_tuning.run_if(resource_changed::<Tuning>),
+    );
 
     app.register_type::<PlayerMovementState>();
 }
@@ -57,10 +62,15 @@ pub struct PlayerMovementBundle {
 }
 
 impl PlayerMovementBundle {
-    pub fn new(collider: Collider, scale: Vector) -> Self {
+    pub fn new(collider: Collider, scale: Vector, tuning: &PlayerTuning) -> Self {
         Self {
             state: PlayerMovementState::Idle(false),
-            physics: CreaturePhysicsBundle::new(collider, scale, MOVEMENT_DAMPING, MAX_SLOPE_ANGLE),
+            physics: CreaturePhysicsBundle::new(
+                collider,
+                scale,
+                tuning.movement_damping,
+                MAX_SLOPE_ANGLE,
+            ),
         }
     }
 }
@@ -82,6 +92,7 @@ fn movement(
     mut dash_event_writer: EventWriter<DashingEvent>,
     mut sprite_change_event: EventWriter<SpriteImageChange>,
     controller: Single<(&mut Player, &mut LinearVelocity, &mut PlayerMovementState)>,
+    tuning: Res<Tuning>,
 ) {
     // Precision is adjusted so that the example works with
     // both the `f32` and `f64` features. Otherwise you don't need this.
@@ -103,7 +114,8 @@ fn movement(
                     }
 
                     player.face_direction = *direction;
-                    let desired_speed = direction.x * MOVEMENT_SPEED - linear_velocity.x;
+                    let desired_speed =
+                        direction.x * tuning.player.movement_speed - linear_velocity.x;
                     linear_velocity.x += desired_speed * 10. * delta_time;
 
                     if let PlayerMovementState::Idle(_) = *player_movement_state {
@@ -124,3 +136,13 @@ fn movement(
         }
     }
 }
+
+/// The damping is set when the player spawns, so it has to be updated when it's retuned.
+fn apply_movement_damping_tuning(
+    tuning: Res<Tuning>,
+    mut damping: Query<&mut MovementDampingFactor, With<Player>>,
+) {
+    for mut damping in &mut damping {
+        damping.0 = tuning.player.movement_damping;
+    }
+}
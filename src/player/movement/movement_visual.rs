@@ -10,6 +10,7 @@ use crate::{
         character::{Player, PlayerAssets, PlayerLayoutAssets, player_sprite},
         movement::movement::PlayerMovementState,
     },
+    rng::GameRng,
 };
 const IDLE_FRAME_NUM: usize = 10;
 const RUN_FRAME_NUM: usize = 32;
@@ -54,6 +55,7 @@ fn update_player_sprite_animation(
     animation: Res<Animation>,
     player_assets: Res<PlayerAssets>,
     mut commands: Commands,
+    mut rng: ResMut<GameRng>,
 ) {
     let (sprite, player_mode, grounded) = &mut *player;
 
@@ -78,10 +80,9 @@ fn update_player_sprite_animation(
         PlayerMovementState::Run => {
             texture_atlas.index = (texture_atlas.index + 1) % RUN_FRAME_NUM;
             if texture_atlas.index == 4 || texture_atlas.index == 19 && *grounded {
-                let rng = &mut rand::thread_rng();
                 let chosen = player_assets
                     .player_step_sounds
-                    .choose(rng)
+                    .choose(&mut rng.audio)
                     .unwrap()
                     .clone();
                 commands.spawn(sound_effect(chosen));
@@ -0,0 +1,161 @@
+//! Things enemies throw or shoot that fly on their own, hurt what they hit and disappear when
+//! they hit the ground.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    collision_layers::{GameLayer, enemy_hit_boxes, player_hit_boxes},
+    health::{HitBox, hitbox_prefab},
+    player::{attack::systems::WowTheWeaponHit, character::Player, weapon::WeaponHitbox},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (reflect_projectiles, move_projectiles)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+/// How long a projectile waits before it can hit the same thing again, if it pierces.
+const PROJECTILE_REHIT_DELAY: f32 = 0.5;
+/// Reflected projectiles come back this much faster.
+pub const REFLECT_SPEEDUP: f32 = 1.5;
+
+#[derive(Component)]
+pub struct Projectile {
+    /// Downwards acceleration, in pixels per second squared.
+    pub gravity: f32,
+    /// Seconds until it despawns.
+    pub lifetime: f32,
+    /// Whether the player's weapon can knock it back towards enemies.
+    pub reflectable: bool,
+    /// Keeps going after hurting something.
+    pub pierces: bool,
+}
+
+impl Projectile {
+    pub fn new(lifetime: f32) -> Self {
+        Self {
+            gravity: 0.0,
+            lifetime,
+            reflectable: false,
+            pierces: false,
+        }
+    }
+
+    pub fn reflectable(mut self) -> Self {
+        self.reflectable = true;
+        self
+    }
+
+    pub fn piercing(mut self) -> Self {
+        self.pierces = true;
+        self
+    }
+}
+
+/// A projectile flying at `velocity`. Give it a [`projectile_hitbox`] child to hurt things.
+pub fn projectile(projectile: Projectile, velocity: Vec2) -> impl Bundle {
+    (
+        projectile,
+        // Moved by its velocity, but not pushed around by anything.
+        RigidBody::Kinematic,
+        LinearVelocity(velocity),
+    )
+}
+
+/// Marks the hitbox child of a [`Projectile`].
+#[derive(Component)]
+pub struct ProjectileHitbox;
+
+/// Hurts the player, or enemies once reflected.
+pub fn projectile_hitbox(collider: Collider, damage: f32, transform: Transform) -> impl Bundle {
+    (
+        Name::new("Projectile Hitbox"),
+        ProjectileHitbox,
+        hitbox_prefab(
+            collider,
+            enemy_hit_boxes(),
+            PROJECTILE_REHIT_DELAY,
+            damage,
+            transform,
+        ),
+        CollidingEntities::default(),
+    )
+}
+
+fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut projectiles: Query<(Entity, &mut Projectile, &mut LinearVelocity, &Transform)>,
+    hitboxes: Query<(&ChildOf, &HitBox), With<ProjectileHitbox>>,
+) {
+    let delta_time = time.delta_secs();
+    for (entity, mut projectile, mut velocity, transform) in &mut projectiles {
+        projectile.lifetime -= delta_time;
+        velocity.y -= projectile.gravity * delta_time;
+
+        // Look ahead for ground, so fast projectiles can't skip through thin platforms.
+        let step = velocity.0 * delta_time;
+        let hits_ground = Dir2::new(step).is_ok_and(|direction| {
+            spatial_query
+                .cast_ray(
+                    transform.translation.truncate(),
+                    direction,
+                    step.length(),
+                    true,
+                    &SpatialQueryFilter::from_mask(GameLayer::Ground),
+                )
+                .is_some()
+        });
+        let hit_something = !projectile.pierces
+            && hitboxes.iter().any(|(parent, hitbox)| {
+                parent.parent() == entity && !hitbox.remaining_rehit_delays.is_empty()
+            });
+
+        if projectile.lifetime < 0.0 || hits_ground || hit_something {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Sends reflectable projectiles hit by the player's weapon back the way they came, as the
+/// player's own.
+fn reflect_projectiles(
+    mut wow_the_weapon_hit: EventWriter<WowTheWeaponHit>,
+    player: Single<&Transform, With<Player>>,
+    mut projectiles: Query<(&mut Projectile, &mut LinearVelocity, &Transform), Without<Player>>,
+    mut hitboxes: Query<
+        (
+            &ChildOf,
+            &CollidingEntities,
+            &mut CollisionLayers,
+            &mut HitBox,
+        ),
+        With<ProjectileHitbox>,
+    >,
+    weapons: Query<(), With<WeaponHitbox>>,
+) {
+    for (parent, colliding, mut layers, mut hitbox) in &mut hitboxes {
+        let Ok((mut projectile, mut velocity, transform)) = projectiles.get_mut(parent.parent())
+        else {
+            continue;
+        };
+        if !projectile.reflectable || !colliding.iter().any(|entity| weapons.contains(*entity)) {
+            continue;
+        }
+        let away = (transform.translation - player.translation)
+            .truncate()
+            .normalize_or(-velocity.0.normalize_or_zero());
+        velocity.0 = away * velocity.length() * REFLECT_SPEEDUP;
+        projectile.reflectable = false;
+        *layers = player_hit_boxes();
+        hitbox.remaining_rehit_delays.clear();
+        wow_the_weapon_hit.write(WowTheWeaponHit);
+    }
+}
@@ -0,0 +1,75 @@
+//! A single seedable source of randomness for the whole game.
+//!
+//! Everything that rolls dice should pull from one of the [`GameRng`] streams rather than
+//! `rand::thread_rng()`, so a session can be replayed by passing the same `--seed`.
+
+use bevy::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(GameRng::from_args());
+    app.add_systems(OnEnter(Screen::Gameplay), reseed_game_rng);
+}
+
+const SEED_ARG: &str = "--seed";
+
+// Offsets used to derive the sub-streams from the session seed.
+const AI_STREAM: u64 = 0;
+const AUDIO_STREAM: u64 = 1;
+const SPAWN_STREAM: u64 = 2;
+const LOOT_STREAM: u64 = 3;
+
+#[derive(Resource)]
+pub struct GameRng {
+    /// Seed pinned from the command line, if any. Otherwise each session rolls a fresh one.
+    fixed_seed: Option<u64>,
+    /// Seed of the current session.
+    seed: u64,
+    /// Enemy decision making. Only gameplay logic should pull from this.
+    pub ai: StdRng,
+    /// Cosmetic variety, e.g. which footstep or slash sound plays.
+    pub audio: StdRng,
+    /// How long spawn portals stay open. Never used for where enemies appear, so scripted
+    /// positions stay exact.
+    pub spawn: StdRng,
+    /// What enemies drop when they die.
+    pub loot: StdRng,
+}
+
+impl GameRng {
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        let seed = fixed_seed.unwrap_or_else(rand::random);
+        Self {
+            fixed_seed,
+            seed,
+            ai: StdRng::seed_from_u64(seed.wrapping_add(AI_STREAM)),
+            audio: StdRng::seed_from_u64(seed.wrapping_add(AUDIO_STREAM)),
+            spawn: StdRng::seed_from_u64(seed.wrapping_add(SPAWN_STREAM)),
+            loot: StdRng::seed_from_u64(seed.wrapping_add(LOOT_STREAM)),
+        }
+    }
+
+    /// Reads the seed from `--seed <u64>` if it was passed.
+    fn from_args() -> Self {
+        let mut args = std::env::args().skip_while(|arg| arg != SEED_ARG).skip(1);
+        let fixed_seed = args.next().and_then(|seed| match seed.parse() {
+            Ok(seed) => Some(seed),
+            Err(_) => {
+                warn!("Ignoring invalid {SEED_ARG} value: {seed}");
+                None
+            }
+        });
+        Self::new(fixed_seed)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+fn reseed_game_rng(mut rng: ResMut<GameRng>) {
+    *rng = GameRng::new(rng.fixed_seed);
+    info!("Game RNG seed: {}", rng.seed());
+}
@@ -1,7 +1,8 @@
 pub mod script;
+pub mod spawning;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(script::plugin);
+    app.add_plugins((script::plugin, spawning::plugin));
 }
@@ -1,16 +1,18 @@
-use std::collections::{VecDeque, vec_deque};
+use std::{
+    collections::{VecDeque, vec_deque},
+    convert::Infallible,
+    str::FromStr,
+};
 
 use avian2d::math::AdjustPrecision;
 use bevy::prelude::*;
 use bevy::{
     app::{App, Update},
-    asset::Assets,
     ecs::{
         event::EventReader,
         resource::Resource,
         system::{Commands, Query, Res, ResMut},
     },
-    image::TextureAtlasLayout,
     math::Vec2,
     time::Time,
     ui::Val::*,
@@ -19,29 +21,37 @@ use bevy::{
 use crate::PausableSystems;
 use crate::asset_tracking::LoadResource;
 use crate::enemy::boss::BossController;
-use crate::enemy::configs::POSITION_1;
+use crate::enemy::configs::{
+    BOTLEFT_SPAWN, BOTRIGHT_SPAWN, POSITION_1, TOPLEFT_SPAWN, TOPRIGHT_SPAWN,
+};
 use crate::level::arena::LevelAssets;
 use crate::menus::Menu;
+use crate::rng::GameRng;
 use crate::screens::Screen;
 use crate::screens::title::TitleAssets;
 use crate::{
     enemy::{
-        boss::boss,
-        eye::EyeAssets,
-        slime::{SlimeAssets, SlimeController, slime},
+        caster::CasterController, elite::EliteModifier, imp::ImpController, slime::SlimeController,
+        wisp::WispController,
     },
     health::DeathEvent,
+    script::spawning::{PortalAssets, SpawnPortal, spawn_portal},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         process_script_events
-            .run_if(resource_exists::<EyeAssets>)
-            .run_if(resource_exists::<EyeAssets>)
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
+    app.add_event::<ResetScript>();
+    app.add_systems(
+        Update,
+        reset_script
+            .before(process_script_events)
+            .run_if(on_event::<ResetScript>),
+    );
     app.add_systems(
         Update,
         (progress_dialogue, update_dialogue_text)
@@ -50,40 +60,142 @@ pub(super) fn plugin(app: &mut App) {
     );
     app.insert_resource(get_game_script());
 }
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum Enemy {
     Boss,
     BlackSlime,
     RedSlime,
+    SplittingSlime,
+    BouncerSlime,
+    ArmoredSlime,
+    Imp,
+    Wisp,
+    Caster,
 }
 
+#[derive(Debug)]
 pub enum ScriptEvent {
     Wait(f32),
     WaitForSlimesDead,
     WaitForBossDead,
-    Spawn(Enemy, Vec2),
+    /// Spawns an enemy, made elite by the given modifiers unless there are none.
+    Spawn(Enemy, Vec2, &'static [EliteModifier]),
     Dialogue(&'static str, &'static str),
+    /// Marks a place in the script that can be jumped to, see [`ScriptPosition`].
+    Label(&'static str),
     EndTheGame,
     None,
 }
 
+/// Starts the script over, removing every enemy, including the ones that haven't come through
+/// their portal yet.
+#[derive(Event, Debug, Default)]
+pub struct ResetScript {
+    /// Where to start, the beginning of the script by default. Enemies spawned before this that
+    /// would still be alive get spawned right away.
+    pub start_at: ScriptPosition,
+    /// Leave out every [`ScriptEvent::Dialogue`].
+    pub skip_dialogue: bool,
+}
+
+/// A place in the game script, either the index of an event or the name of a
+/// [`ScriptEvent::Label`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ScriptPosition {
+    Index(usize),
+    Label(String),
+}
+
+impl Default for ScriptPosition {
+    fn default() -> Self {
+        Self::Index(0)
+    }
+}
+
+impl FromStr for ScriptPosition {
+    type Err = Infallible;
+
+    /// Numbers are indices, anything else is a label.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse()
+            .map_or_else(|_| Self::Label(s.to_string()), Self::Index))
+    }
+}
+
+impl ScriptPosition {
+    /// Where this is in the game script, if it's in there at all.
+    pub fn index(&self) -> Option<usize> {
+        let script = get_game_script();
+        match self {
+            Self::Index(index) => (*index < script.queue.len()).then_some(*index),
+            Self::Label(label) => script
+                .queue
+                .iter()
+                .position(|event| matches!(event, ScriptEvent::Label(name) if name == label)),
+        }
+    }
+}
+
+/// How long [`ScriptEvent::WaitForSlimesDead`] waits before giving up on the remaining slimes.
+const SLIME_WAIT_TIMEOUT: f32 = 120.0;
+
 #[derive(Resource, Default)]
 pub struct ScriptEventQueue {
     pub queue: VecDeque<ScriptEvent>,
+    /// Continue past [`ScriptEvent::WaitForSlimesDead`] after this many seconds, so a slime
+    /// that can't be reached doesn't softlock the game. `None` waits forever.
+    pub wait_timeout: Option<f32>,
+    /// How long the event at the front of the queue has been waiting.
+    waited: f32,
+}
+
+impl ScriptEventQueue {
+    /// Drops the event at the front of the queue without waiting for it to finish. The final
+    /// [`ScriptEvent::None`] is never dropped.
+    pub fn skip(&mut self) -> Option<ScriptEvent> {
+        if matches!(self.queue.front(), None | Some(ScriptEvent::None)) {
+            return None;
+        }
+        self.waited = 0.0;
+        self.queue.pop_front()
+    }
+
+    /// Drops the first `count` events, but keeps what they would have left behind: enemies that
+    /// nothing waited to see dead yet, and the end of the game.
+    fn fast_forward(&mut self, count: usize) {
+        let mut boss = None;
+        let mut enemies = Vec::new();
+        let mut end = None;
+        for event in self.queue.drain(..count) {
+            match event {
+                ScriptEvent::Spawn(Enemy::Boss, ..) => boss = Some(event),
+                ScriptEvent::Spawn(..) => enemies.push(event),
+                ScriptEvent::WaitForSlimesDead => enemies.clear(),
+                ScriptEvent::WaitForBossDead => boss = None,
+                ScriptEvent::EndTheGame => end = Some(event),
+                _ => {}
+            }
+        }
+        let kept: Vec<_> = boss.into_iter().chain(enemies).chain(end).collect();
+        for event in kept.into_iter().rev() {
+            self.queue.push_front(event);
+        }
+    }
 }
 
 fn get_game_script() -> ScriptEventQueue {
     //TODO: find these actual spawns?! and/or remove the ones that the player might be standing on.
-    let botleft_spawn = Vec2::new(-500.0, 100.0);
-    let botright_spawn = Vec2::new(500.0, 100.0);
-    let topleft_spawn = Vec2::new(-500.0, 200.0);
-    let topright_spawn = Vec2::new(500.0, 200.0);
+    let botleft_spawn = BOTLEFT_SPAWN;
+    let botright_spawn = BOTRIGHT_SPAWN;
+    let topleft_spawn = TOPLEFT_SPAWN;
+    let topright_spawn = TOPRIGHT_SPAWN;
     let topmiddle_spawn = Vec2::new(0.0, 200.0);
     let topleft_sky_spawn = Vec2::new(-100.0, 400.0);
     let topright_sky_spawn = Vec2::new(100.0, 400.0);
     let topmiddle_sky_spawn = Vec2::new(0.0, 400.0);
     let boss_spawn = POSITION_1;
     let queue = vec![
+        ScriptEvent::Label("intro"),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::Wait(3.0),
         ScriptEvent::Dialogue("Narrator","[As Ali regains consciousness, a voice echoes in his mind, sharp and demanding.]"),
@@ -105,11 +217,12 @@ fn get_game_script() -> ScriptEventQueue {
         ScriptEvent::Dialogue("Mysterious Voice","Slimes. They're weak, but there will be many more. Use the axe get ready!"),
         ScriptEvent::Dialogue("Tip","Hold or press X to attack. As your weapon chain reacts from hitting enemies to gain fury (Red), its swiftness increases. Attack again during the reset period to continue the chain reaction, or miss and go into the cooldown phase (blue)."),
         ScriptEvent::Wait(1.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topright_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::Wait(3.0),
+        ScriptEvent::Label("asad"),
         ScriptEvent::Dialogue("Mysterious Voice","Well done, Ali. You've survived the first wave."),
         ScriptEvent::Dialogue("Ali","Are you gonna tell me what's going on now?"),
         ScriptEvent::Dialogue("Mysterious Voice","My name is Asad. I sealed myself away with Zha'kthar, an ancient monster. The seal has weakened, and I've seen a vision you are the one who can stop him."),
@@ -118,39 +231,41 @@ fn get_game_script() -> ScriptEventQueue {
         ScriptEvent::Dialogue("Zha'kthar","You will fail!"),
         ScriptEvent::Dialogue("Asad","Stay focused, the real battle is just beginning."),
         ScriptEvent::Wait(1.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topright_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
-        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
-        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn,&[]),
         ScriptEvent::Wait(1.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topright_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::Wait(3.0),
+        ScriptEvent::Label("red_slimes"),
         ScriptEvent::Dialogue("Asad","The seal weakens faster. Zha'kthar senses you now."),
         ScriptEvent::Dialogue("Ali","What do I do? I don't even know what's going on!"),
         ScriptEvent::Dialogue("Asad","You must stop him before he breaks free completely. It's your only choice."),
         ScriptEvent::Dialogue("Zha'kthar","You are nothing. I will destroy you!"),
         ScriptEvent::Dialogue("Asad","Focus! The next wave is worse."),
         ScriptEvent::Wait(1.0),
-        ScriptEvent::Spawn(Enemy::RedSlime,topleft_sky_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topright_sky_spawn),
+        ScriptEvent::Spawn(Enemy::RedSlime,topleft_sky_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topright_sky_spawn,&[]),
         ScriptEvent::Wait(5.0),
-        ScriptEvent::Spawn(Enemy::RedSlime,topleft_sky_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topright_sky_spawn),
+        ScriptEvent::Spawn(Enemy::RedSlime,topleft_sky_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topright_sky_spawn,&[]),
         ScriptEvent::Wait(5.0),
-        ScriptEvent::Spawn(Enemy::RedSlime,topleft_sky_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topright_sky_spawn),
+        ScriptEvent::Spawn(Enemy::RedSlime,topleft_sky_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topright_sky_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::Wait(3.0),
+        ScriptEvent::Label("swarm"),
         ScriptEvent::Dialogue("Ali","I can't keep this up!"),
         ScriptEvent::Dialogue("Asad","You can. The only way out is through him. Zha'kthar's power is growing."),
         ScriptEvent::Dialogue("Ali","I'm not ready!"),
@@ -158,48 +273,50 @@ fn get_game_script() -> ScriptEventQueue {
         ScriptEvent::Dialogue("Zha'kthar","You think you can stop me? You're weak!"),
         ScriptEvent::Dialogue("Asad","You're not weak, Ali. You have what it takes. Don't doubt yourself."),
         ScriptEvent::Wait(1.0),
-        ScriptEvent::Spawn(Enemy::RedSlime,topleft_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topright_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,botright_spawn),
+        ScriptEvent::Spawn(Enemy::RedSlime,topleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topright_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,botright_spawn,&[]),
         ScriptEvent::Wait(10.0),
         ScriptEvent::WaitForSlimesDead,
-        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_sky_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,topright_sky_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topmiddle_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topleft_sky_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,topright_sky_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topmiddle_spawn,&[]),
         ScriptEvent::Wait(3.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn,&[]),
         ScriptEvent::Wait(3.0),
-        ScriptEvent::Spawn(Enemy::RedSlime,topmiddle_sky_spawn),
+        ScriptEvent::Spawn(Enemy::RedSlime,topmiddle_sky_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::Wait(3.0),
+        ScriptEvent::Label("boss"),
         ScriptEvent::Dialogue("Asad","This is it. Zha'kthar's final form is coming."),
         ScriptEvent::Dialogue("Ali","I don't know if I can do this..."),
         ScriptEvent::Dialogue("Asad","You must. This is your moment."),
         ScriptEvent::Dialogue("Zha'kthar","You cannot defeat me. I will consume you!"),
         ScriptEvent::Dialogue("Asad","You've come this far. Now finish this."),
         ScriptEvent::Wait(1.0),
-        ScriptEvent::Spawn(Enemy::Boss,boss_spawn),
+        ScriptEvent::Spawn(Enemy::Boss,boss_spawn,&[]),
         //TODO: maybe add to this part of the queue as the boss fight happens? A hacky way to do it would be to have the boss controller spawn slimes
         ScriptEvent::Wait(15.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topright_spawn,&[]),
         ScriptEvent::Wait(10.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,topleft_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,topleft_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::Wait(5.0),
-        ScriptEvent::Spawn(Enemy::RedSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::RedSlime,botright_spawn),
+        ScriptEvent::Spawn(Enemy::RedSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::RedSlime,botright_spawn,&[]),
         ScriptEvent::Wait(5.0),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn),
-        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botleft_spawn,&[]),
+        ScriptEvent::Spawn(Enemy::BlackSlime,botright_spawn,&[]),
         ScriptEvent::Wait(0.1),
         ScriptEvent::WaitForSlimesDead,
         ScriptEvent::WaitForBossDead,
+        ScriptEvent::Label("ending"),
         ScriptEvent::Wait(3.0),
         ScriptEvent::Dialogue("Ali","I... I did it. I stopped him."),
         ScriptEvent::Dialogue("Asad","You've slain Zha'kthar, but that was only part of the chain. The seal is weakened now. The storm is far from over."),
@@ -219,23 +336,82 @@ fn get_game_script() -> ScriptEventQueue {
         ScriptEvent::None,
     ]
     .into();
-    ScriptEventQueue { queue }
+    ScriptEventQueue {
+        queue,
+        wait_timeout: Some(SLIME_WAIT_TIMEOUT),
+        waited: 0.0,
+    }
+}
+
+fn reset_script(
+    mut commands: Commands,
+    mut resets: EventReader<ResetScript>,
+    mut script_events: ResMut<ScriptEventQueue>,
+    enemies: Query<
+        Entity,
+        Or<(
+            With<SlimeController>,
+            With<ImpController>,
+            With<WispController>,
+            With<CasterController>,
+            With<BossController>,
+            With<SpawnPortal>,
+        )>,
+    >,
+    mut dialogue: Query<&mut Visibility, With<Dialogue>>,
+) {
+    let Some(reset) = resets.read().last() else {
+        return;
+    };
+    let Some(start) = reset.start_at.index() else {
+        warn!(
+            "Can't reset the script to {:?}, it isn't in there",
+            reset.start_at
+        );
+        return;
+    };
+    let mut script = get_game_script();
+    script.fast_forward(start);
+    if reset.skip_dialogue {
+        script
+            .queue
+            .retain(|event| !matches!(event, ScriptEvent::Dialogue(..)));
+    }
+    *script_events = script;
+    for enemy in &enemies {
+        commands.entity(enemy).despawn();
+    }
+    // Shown again if the script starts on a dialogue.
+    for mut visibility in &mut dialogue {
+        *visibility = Visibility::Hidden;
+    }
 }
 
 fn process_script_events(
     mut commands: Commands,
     time: Res<Time>,
-    slime_assets: Res<SlimeAssets>,
-    eye_assets: Res<EyeAssets>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    portal_assets: Res<PortalAssets>,
     mut script_events: ResMut<ScriptEventQueue>,
-    slimes: Query<&SlimeController>,
+    mut rng: ResMut<GameRng>,
+    slimes: Query<
+        (),
+        Or<(
+            With<SlimeController>,
+            With<ImpController>,
+            With<WispController>,
+            With<CasterController>,
+            With<SpawnPortal>,
+        )>,
+    >,
     bosses: Query<&BossController>,
+    portals: Query<&SpawnPortal>,
     mut next_menu: ResMut<NextState<Menu>>,
     mut next_screen: ResMut<NextState<Screen>>,
     dialogue: Single<(&mut Dialogue, &mut Visibility)>,
 ) {
     let mut delta = time.delta_secs().adjust_precision();
+    let script_events = script_events.into_inner();
+    script_events.waited += delta;
     loop {
         if let Some(event) = script_events.queue.get_mut(0) {
             match event {
@@ -246,28 +422,36 @@ fn process_script_events(
                     }
                     delta = -*remaining_time;
                 }
-                ScriptEvent::Spawn(enemy, position) => match enemy {
-                    Enemy::Boss => {
-                        commands.spawn(boss(
-                            &eye_assets,
-                            &mut texture_atlas_layouts,
-                            position.extend(0.3),
-                        ));
-                    }
-                    Enemy::BlackSlime => {
-                        commands.spawn(slime(&slime_assets, position.extend(0.), false));
-                    }
-                    Enemy::RedSlime => {
-                        commands.spawn(slime(&slime_assets, position.extend(0.), true));
-                    }
-                },
+                ScriptEvent::Spawn(enemy, position, modifiers) => {
+                    let translation = spawn_translation(*enemy, *position);
+                    spawn_portal(
+                        &mut commands,
+                        &portal_assets,
+                        *enemy,
+                        translation,
+                        modifiers,
+                        &mut rng.spawn,
+                    );
+                }
                 ScriptEvent::WaitForSlimesDead => {
-                    if !slimes.is_empty() {
+                    let timed_out = script_events
+                        .wait_timeout
+                        .is_some_and(|timeout| script_events.waited > timeout);
+                    if timed_out {
+                        warn!(
+                            "Gave up waiting for slimes after {:.0}s with {} still alive, continuing",
+                            script_events.waited,
+                            slimes.iter().len()
+                        );
+                    } else if !slimes.is_empty() {
                         break;
                     }
                 }
                 ScriptEvent::WaitForBossDead => {
-                    if !bosses.is_empty() {
+                    let boss_arriving = portals
+                        .iter()
+                        .any(|portal| matches!(portal.enemy(), Enemy::Boss));
+                    if !bosses.is_empty() || boss_arriving {
                         break;
                     }
                 }
@@ -278,6 +462,7 @@ fn process_script_events(
                     *visibility = Visibility::Inherited;
                     break;
                 }
+                ScriptEvent::Label(_) => {}
                 ScriptEvent::EndTheGame => {
                     next_menu.set(Menu::Results);
                 }
@@ -286,10 +471,20 @@ fn process_script_events(
                 }
             }
             script_events.queue.remove(0); // Don't increment i — we just removed this item
+            script_events.waited = 0.0;
+        } else {
+            break;
         }
     }
 }
 
+fn spawn_translation(enemy: Enemy, position: Vec2) -> Vec3 {
+    match enemy {
+        Enemy::Boss => position.extend(0.3),
+        _ => position.extend(0.),
+    }
+}
+
 fn progress_dialogue(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut script_events: ResMut<ScriptEventQueue>,
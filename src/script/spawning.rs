@@ -0,0 +1,248 @@
+//! How enemies from [`ScriptEvent::Spawn`] enter the arena.
+//!
+//! A [`SpawnPortal`] first marks the spot for about [`ENEMY_SPAWN_TELEGRAPH`] seconds, give or
+//! take [`SPAWN_TELEGRAPH_JITTER`]. Then the enemy
+//! appears and fades in over [`ENEMY_MATERIALIZE_DURATION`] seconds with its hitboxes disabled
+//! and its AI on hold, so it can't hurt the player or start an attack before it's fully there.
+//!
+//! [`ScriptEvent::Spawn`]: super::script::ScriptEvent::Spawn
+
+use avian2d::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+
+use crate::{
+    PausableSystems,
+    enemy::{
+        boss::boss,
+        caster::{CasterAssets, caster},
+        configs::*,
+        elite::{Elite, EliteModifier},
+        eye::EyeAssets,
+        imp::{ImpAssets, imp},
+        slime::{SlimeAssets, SlimeKind, slime},
+        wisp::{WispAssets, wisp},
+    },
+    health::HitBox,
+    rng::GameRng,
+    screens::Screen,
+    script::script::Enemy,
+    tuning::Tuning,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PortalAssets>();
+    app.add_systems(
+        Update,
+        (
+            open_spawn_portals
+                .run_if(resource_exists::<SlimeAssets>)
+                .run_if(resource_exists::<EyeAssets>)
+                .run_if(resource_exists::<ImpAssets>)
+                .run_if(resource_exists::<WispAssets>)
+                .run_if(resource_exists::<CasterAssets>),
+            disable_materializing_hitboxes,
+            materialize_enemies,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Marks where an enemy is about to appear, and spawns it once `time_remaining` runs out.
+#[derive(Component)]
+pub struct SpawnPortal {
+    enemy: Enemy,
+    modifiers: &'static [EliteModifier],
+    /// How long this portal stays open in total.
+    telegraph: f32,
+    time_remaining: f32,
+}
+
+impl SpawnPortal {
+    pub fn enemy(&self) -> Enemy {
+        self.enemy
+    }
+}
+
+/// A freshly spawned enemy fading in. Its hitboxes are disabled and it doesn't think or act
+/// until this is removed.
+#[derive(Component)]
+pub struct Materializing {
+    time_remaining: f32,
+}
+
+#[derive(Resource)]
+pub(super) struct PortalAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+}
+
+impl FromWorld for PortalAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Ellipse::new(
+            SPAWN_PORTAL_SIZE.x / 2.0,
+            SPAWN_PORTAL_SIZE.y / 2.0,
+        ));
+        let material = world
+            .resource_mut::<Assets<ColorMaterial>>()
+            .add(Color::srgba(0.35, 0.05, 0.45, 0.8));
+        Self { mesh, material }
+    }
+}
+
+/// Spawns a portal for `enemy` at `translation`.
+pub(super) fn spawn_portal(
+    commands: &mut Commands,
+    portal_assets: &PortalAssets,
+    enemy: Enemy,
+    translation: Vec3,
+    modifiers: &'static [EliteModifier],
+    rng: &mut impl Rng,
+) {
+    let telegraph =
+        ENEMY_SPAWN_TELEGRAPH + rng.gen_range(-SPAWN_TELEGRAPH_JITTER..=SPAWN_TELEGRAPH_JITTER);
+    commands.spawn((
+        Name::new(format!("{enemy:?} Spawn Portal")),
+        SpawnPortal {
+            enemy,
+            modifiers,
+            telegraph,
+            time_remaining: telegraph,
+        },
+        Mesh2d(portal_assets.mesh.clone()),
+        MeshMaterial2d(portal_assets.material.clone()),
+        // Starts as a point and opens up until the enemy comes through.
+        Transform::from_translation(translation).with_scale(Vec3::ZERO),
+        StateScoped(Screen::Gameplay),
+    ));
+}
+
+/// Everything needed to spawn any kind of [`Enemy`].
+#[derive(SystemParam)]
+struct EnemySpawner<'w> {
+    slime_assets: Res<'w, SlimeAssets>,
+    imp_assets: Res<'w, ImpAssets>,
+    wisp_assets: Res<'w, WispAssets>,
+    caster_assets: Res<'w, CasterAssets>,
+    eye_assets: Res<'w, EyeAssets>,
+    texture_atlas_layouts: ResMut<'w, Assets<TextureAtlasLayout>>,
+    rng: ResMut<'w, GameRng>,
+    tuning: Res<'w, Tuning>,
+}
+
+impl EnemySpawner<'_> {
+    fn spawn(&mut self, commands: &mut Commands, enemy: Enemy, translation: Vec3) -> Entity {
+        let rng = &mut self.rng.ai;
+        let tuning = &self.tuning.enemy;
+        match enemy {
+            Enemy::Boss => commands
+                .spawn(boss(
+                    &self.eye_assets,
+                    &mut self.texture_atlas_layouts,
+                    translation,
+                    tuning,
+                ))
+                .id(),
+            Enemy::BlackSlime
+            | Enemy::RedSlime
+            | Enemy::SplittingSlime
+            | Enemy::BouncerSlime
+            | Enemy::ArmoredSlime => {
+                let kind = match enemy {
+                    Enemy::RedSlime => SlimeKind::Red,
+                    Enemy::SplittingSlime => SlimeKind::Splitting,
+                    Enemy::BouncerSlime => SlimeKind::Bouncer,
+                    Enemy::ArmoredSlime => SlimeKind::Armored,
+                    _ => SlimeKind::Black,
+                };
+                commands
+                    .spawn(slime(&self.slime_assets, translation, kind, rng, tuning))
+                    .id()
+            }
+            Enemy::Imp => commands
+                .spawn(imp(&self.imp_assets, translation, tuning))
+                .id(),
+            Enemy::Wisp => commands
+                .spawn(wisp(&self.wisp_assets, translation, rng, tuning))
+                .id(),
+            Enemy::Caster => commands
+                .spawn(caster(&self.caster_assets, translation, rng, tuning))
+                .id(),
+        }
+    }
+}
+
+fn open_spawn_portals(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut spawner: EnemySpawner,
+    mut portals: Query<(Entity, &mut SpawnPortal, &mut Transform)>,
+) {
+    for (entity, mut portal, mut transform) in &mut portals {
+        portal.time_remaining -= time.delta_secs();
+        let progress = 1.0 - (portal.time_remaining / portal.telegraph).max(0.0);
+        transform.scale = Vec3::splat(progress);
+        if portal.time_remaining > 0.0 {
+            continue;
+        }
+
+        let enemy = spawner.spawn(&mut commands, portal.enemy, transform.translation);
+        commands.entity(enemy).insert(Materializing {
+            time_remaining: ENEMY_MATERIALIZE_DURATION,
+        });
+        if !portal.modifiers.is_empty() {
+            commands
+                .entity(enemy)
+                .insert(Elite(portal.modifiers.to_vec()));
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+fn disable_materializing_hitboxes(
+    mut commands: Commands,
+    enemies: Query<&Children, Added<Materializing>>,
+    hitboxes: Query<(), With<HitBox>>,
+) {
+    for children in &enemies {
+        for child in children {
+            if hitboxes.contains(*child) {
+                commands.entity(*child).insert(ColliderDisabled);
+            }
+        }
+    }
+}
+
+fn materialize_enemies(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemies: Query<(
+        Entity,
+        &mut Materializing,
+        Option<&mut Sprite>,
+        Option<&Children>,
+    )>,
+    hitboxes: Query<(), With<HitBox>>,
+) {
+    for (entity, mut materializing, sprite, children) in &mut enemies {
+        materializing.time_remaining -= time.delta_secs();
+        let progress = 1.0 - (materializing.time_remaining / ENEMY_MATERIALIZE_DURATION).max(0.0);
+        if let Some(mut sprite) = sprite {
+            sprite.color.set_alpha(progress);
+        }
+        if materializing.time_remaining > 0.0 {
+            continue;
+        }
+
+        commands.entity(entity).remove::<Materializing>();
+        for child in children
+            .into_iter()
+            .flatten()
+            .filter(|child| hitboxes.contains(**child))
+        {
+            commands.entity(*child).remove::<ColliderDisabled>();
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    player::attack::behaviour::{Attack, AttackPhase, InputAttackEvent},
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+#[test]
+fn hitting_increases_fury() {
+    let tuning = Tuning::default().attack;
+    let mut attack = Attack::new(&tuning);
+    let (delay, extend) = (attack.attack_delay_seconds, attack.extend_scale);
+
+    attack.update_fury(true, &tuning);
+
+    assert!(attack.attack_delay_seconds < delay);
+    assert!(attack.extend_scale < extend);
+}
+
+#[test]
+fn missing_decreases_fury() {
+    let tuning = Tuning::default().attack;
+    let mut attack = Attack::new(&tuning);
+    attack.update_fury(true, &tuning);
+    attack.update_fury(true, &tuning);
+    let (delay, extend) = (attack.attack_delay_seconds, attack.extend_scale);
+
+    attack.update_fury(false, &tuning);
+
+    assert!(attack.attack_delay_seconds > delay);
+    assert!(attack.extend_scale > extend);
+}
+
+#[test]
+fn fury_is_clamped_at_both_ends() {
+    let tuning = Tuning::default().attack;
+    let mut attack = Attack::new(&tuning);
+    let (initial_delay, initial_extend) = (attack.attack_delay_seconds, attack.extend_scale);
+
+    attack.update_fury(false, &tuning);
+    assert_eq!(attack.attack_delay_seconds, initial_delay);
+    assert_eq!(attack.extend_scale, initial_extend);
+
+    for _ in 0..100 {
+        attack.update_fury(true, &tuning);
+    }
+    let (min_delay, min_extend) = (attack.attack_delay_seconds, attack.extend_scale);
+    assert!(min_delay > 0.0 && min_extend > 0.0);
+
+    attack.update_fury(true, &tuning);
+    assert_eq!(attack.attack_delay_seconds, min_delay);
+    assert_eq!(attack.extend_scale, min_extend);
+}
+
+#[test]
+fn attack_input_starts_reacting() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+
+    harness.send(InputAttackEvent);
+    harness.step();
+    harness.step();
+
+    let player = harness.player();
+    let attack = harness
+        .world()
+        .get::<Attack>(player)
+        .expect("attack input should give the player an attack");
+    assert!(matches!(attack.phase, AttackPhase::Reacting(_)));
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{
+    audio::Music,
+    difficulty::Difficulty,
+    enemy::{
+        behaviour::StateMachine,
+        boss::{BossState, Lazer, boss},
+        boss_attacks::BOSS_ATTACKS,
+        configs::{BOSS_MUSIC_CROSSFADE, BOSS_PHASES, POSITION_1, RING_LAZER_COUNT},
+        eye::EyeAssets,
+    },
+    health::Health,
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+fn spawn_boss(harness: &mut Harness) -> Entity {
+    let world = harness.world_mut();
+    let eye_assets = world.resource::<EyeAssets>().clone();
+    let tuning = world.resource::<Tuning>().enemy.clone();
+    world.resource_scope(|world, mut layouts: Mut<Assets<TextureAtlasLayout>>| {
+        world
+            .spawn(boss(
+                &eye_assets,
+                &mut layouts,
+                POSITION_1.extend(0.3),
+                &tuning,
+            ))
+            .id()
+    })
+}
+
+/// Makes the boss carry out the named attack once and counts the lazers it spawns.
+fn lazers_in_attack(difficulty: Difficulty, name: &str) -> usize {
+    let mut harness = Harness::new();
+    *harness.world_mut().resource_mut::<Difficulty>() = difficulty;
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let attack = BOSS_ATTACKS
+        .iter()
+        .position(|attack| attack.name == name)
+        .unwrap();
+    let boss = spawn_boss(&mut harness);
+    harness
+        .world_mut()
+        .get_mut::<StateMachine<BossState>>(boss)
+        .unwrap()
+        .transition(BossState::Attack(attack));
+
+    let mut lazers = HashSet::new();
+    let mut started = false;
+    let finished = harness.step_until(Duration::from_secs(10), |world| {
+        lazers.extend(world.query_filtered::<Entity, With<Lazer>>().iter(world));
+        let attacking = world.get::<StateMachine<BossState>>(boss).unwrap().state()
+            == BossState::Attack(attack);
+        started |= attacking;
+        started && !attacking
+    });
+    assert!(finished, "{name} never ended on {difficulty:?}");
+    lazers.len()
+}
+
+#[test]
+fn one_off_attacks_spawn_their_lazers_once_on_every_difficulty() {
+    for difficulty in Difficulty::ALL {
+        assert_eq!(lazers_in_attack(difficulty, "Beam"), 1, "{difficulty:?}");
+        assert_eq!(lazers_in_attack(difficulty, "Sweep"), 1, "{difficulty:?}");
+        assert_eq!(
+            lazers_in_attack(difficulty, "Ring"),
+            RING_LAZER_COUNT,
+            "{difficulty:?}"
+        );
+    }
+}
+
+#[test]
+fn sky_rain_spawns_lazers_at_the_difficulty_density() {
+    let sky_rain = Tuning::default().enemy.boss.sky_rain;
+    for difficulty in Difficulty::ALL {
+        let every = sky_rain.spawn_every.unwrap() / difficulty.scaling().sky_lazer_density;
+        let expected = (sky_rain.duration / every).ceil() as usize;
+        let spawned = lazers_in_attack(difficulty, "Sky Rain");
+        // The last one can land on the frame the attack ends.
+        assert!(
+            spawned.abs_diff(expected) <= 1,
+            "{difficulty:?} spawned {spawned} sky lazers, expected about {expected}"
+        );
+    }
+}
+
+fn music(harness: &mut Harness) -> Vec<Entity> {
+    let world = harness.world_mut();
+    world
+        .query_filtered::<Entity, With<Music>>()
+        .iter(world)
+        .collect()
+}
+
+#[test]
+fn breaking_free_crossfades_into_the_next_phase_music() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+    let level_music = music(&mut harness);
+
+    let boss = spawn_boss(&mut harness);
+    harness.step();
+    let mut health = harness.world_mut().get_mut::<Health>(boss).unwrap();
+    health.current = BOSS_PHASES[1].health_threshold * health.max - 1.0;
+    let unchaining = harness.step_until(Duration::from_secs(1), |world| {
+        world.get::<StateMachine<BossState>>(boss).unwrap().state() == BossState::Unchaining
+    });
+    assert!(unchaining, "the boss should break its chains at low health");
+    harness.step();
+
+    // Both play while one fades into the other.
+    let playing = music(&mut harness);
+    assert!(level_music.iter().all(|music| playing.contains(music)));
+    assert_eq!(playing.len(), level_music.len() + 1);
+
+    harness.step_for(Duration::from_secs_f32(BOSS_MUSIC_CROSSFADE + 0.1));
+    let playing = music(&mut harness);
+    assert_eq!(playing.len(), 1);
+    assert!(!level_music.contains(&playing[0]));
+    let playback = harness.world().get::<PlaybackSettings>(playing[0]).unwrap();
+    assert_eq!(playback.volume.to_linear(), 1.0);
+}
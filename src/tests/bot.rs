@@ -0,0 +1,195 @@
+//! A dumb bot that plays the campaign through the keyboard.
+//!
+//! It presses keys on [`ButtonInput<KeyCode>`] right after Bevy has processed real input, so it goes
+//! through exactly the same input systems as a player would.
+
+use bevy::{input::InputSystem, prelude::*};
+
+use crate::{
+    enemy::{
+        boss::{BossController, Lazer},
+        caster::CasterController,
+        imp::ImpController,
+        slime::SlimeController,
+        wisp::WispController,
+    },
+    health::Health,
+    physics::creature::Grounded,
+    player::{
+        character::Player,
+        configs::{
+            KEYBOARD_ATTACK, KEYBOARD_DASH, KEYBOARD_JUMP, KEYBOARD_LEFT, KEYBOARD_RIGHT,
+            KEYBOARD_UP,
+        },
+    },
+    projectile::Projectile,
+    script::script::{ScriptEvent, ScriptEventQueue},
+};
+
+const KEYBOARD_DIALOGUE: KeyCode = KeyCode::Enter;
+const BOT_KEYS: [KeyCode; 7] = [
+    KEYBOARD_DIALOGUE,
+    KEYBOARD_LEFT,
+    KEYBOARD_RIGHT,
+    KEYBOARD_UP,
+    KEYBOARD_JUMP,
+    KEYBOARD_ATTACK,
+    KEYBOARD_DASH,
+];
+
+// These mirror the platform layout in `level::arena`.
+const SIDE_PLATFORM_TOP: f32 = -205.0;
+const SIDE_PLATFORM_INNER_X: f32 = 570.0;
+const PLAYER_HALF_HEIGHT: f32 = 100.0;
+
+/// Where to stand on the main platform before jumping up to a side platform.
+const LAUNCH_X: f32 = 500.0;
+/// Close enough to stop walking towards something.
+const ARRIVE_DISTANCE: f32 = 15.0;
+const ATTACK_REACH: f32 = 400.0;
+/// Attack upwards when the target is at least this much higher than the player.
+const ATTACK_UP_THRESHOLD: f32 = 100.0;
+/// Step aside from falling sky lasers closer than this horizontally.
+const DODGE_DISTANCE: f32 = 100.0;
+
+#[derive(Resource)]
+pub struct Bot {
+    /// Keeps the player at full health.
+    pub god_mode: bool,
+}
+
+/// Lets the bot take over the player. Call this on an already built [`App`].
+pub fn add_bot(app: &mut App, bot: Bot) {
+    app.insert_resource(bot);
+    app.add_systems(PreUpdate, drive_bot.after(InputSystem));
+    app.add_systems(Last, refill_health.run_if(|bot: Res<Bot>| bot.god_mode));
+}
+
+fn drive_bot(
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    script: Res<ScriptEventQueue>,
+    player: Single<(&Transform, Has<Grounded>), With<Player>>,
+    enemies: Query<
+        &Transform,
+        (
+            Or<(
+                With<SlimeController>,
+                With<BossController>,
+                With<ImpController>,
+                With<WispController>,
+                With<CasterController>,
+            )>,
+            Without<Player>,
+        ),
+    >,
+    sky_lazers: Query<&GlobalTransform, (With<Lazer>, With<Projectile>)>,
+) {
+    let (transform, is_grounded) = player.into_inner();
+    let position = transform.translation.truncate();
+    let mut wanted = Vec::new();
+
+    // Tap through dialogue, it needs a fresh press for every line.
+    if matches!(script.queue.front(), Some(ScriptEvent::Dialogue(..)))
+        && !keys.pressed(KEYBOARD_DIALOGUE)
+    {
+        wanted.push(KEYBOARD_DIALOGUE);
+    }
+
+    let target = enemies
+        .iter()
+        .map(|enemy| enemy.translation.truncate())
+        .min_by(|a, b| {
+            a.distance_squared(position)
+                .total_cmp(&b.distance_squared(position))
+        });
+    if let Some(target) = target {
+        wanted.extend(approach(position, target, is_grounded, &keys));
+        if position.distance(target) < ATTACK_REACH {
+            wanted.push(KEYBOARD_ATTACK);
+            if target.y - position.y > ATTACK_UP_THRESHOLD {
+                wanted.push(KEYBOARD_UP);
+            }
+        }
+    }
+
+    // Getting out of the way of a sky laser beats everything else.
+    if let Some(lazer_x) = sky_lazers
+        .iter()
+        .map(|lazer| lazer.translation().x)
+        .find(|x| (x - position.x).abs() < DODGE_DISTANCE)
+    {
+        wanted.retain(|key| *key != KEYBOARD_LEFT && *key != KEYBOARD_RIGHT);
+        wanted.push(if position.x > lazer_x {
+            KEYBOARD_RIGHT
+        } else {
+            KEYBOARD_LEFT
+        });
+    }
+
+    for key in BOT_KEYS {
+        match (wanted.contains(&key), keys.pressed(key)) {
+            (true, false) => keys.press(key),
+            (false, true) => keys.release(key),
+            _ => {}
+        }
+    }
+}
+
+/// Keys that get the player onto the same platform as `target`, then next to it.
+fn approach(
+    position: Vec2,
+    target: Vec2,
+    is_grounded: bool,
+    keys: &ButtonInput<KeyCode>,
+) -> Vec<KeyCode> {
+    match (on_side_platform(position), on_side_platform(target)) {
+        // Jump from just short of the side platform, then dash over its edge at the top of the jump.
+        (false, true) => {
+            let side = target.x.signum();
+            let feet = position.y - PLAYER_HALF_HEIGHT;
+            if is_grounded {
+                if (position.x - side * LAUNCH_X).abs() > ARRIVE_DISTANCE {
+                    walk_towards(position.x, side * LAUNCH_X)
+                        .into_iter()
+                        .collect()
+                } else if keys.pressed(KEYBOARD_JUMP) {
+                    // Landed back down, let go so the next jump registers.
+                    vec![]
+                } else {
+                    vec![KEYBOARD_JUMP]
+                }
+            } else if feet > SIDE_PLATFORM_TOP {
+                let outwards = if side > 0.0 {
+                    KEYBOARD_RIGHT
+                } else {
+                    KEYBOARD_LEFT
+                };
+                vec![KEYBOARD_JUMP, outwards, KEYBOARD_DASH]
+            } else {
+                vec![KEYBOARD_JUMP]
+            }
+        }
+        // Walk off the inner edge.
+        (true, false) => walk_towards(position.x, 0.0).into_iter().collect(),
+        _ => walk_towards(position.x, target.x).into_iter().collect(),
+    }
+}
+
+fn walk_towards(from_x: f32, to_x: f32) -> Option<KeyCode> {
+    if to_x - from_x > ARRIVE_DISTANCE {
+        Some(KEYBOARD_RIGHT)
+    } else if from_x - to_x > ARRIVE_DISTANCE {
+        Some(KEYBOARD_LEFT)
+    } else {
+        None
+    }
+}
+
+fn on_side_platform(position: Vec2) -> bool {
+    position.y > SIDE_PLATFORM_TOP - PLAYER_HALF_HEIGHT / 2.0
+        && position.x.abs() > SIDE_PLATFORM_INNER_X
+}
+
+fn refill_health(mut player: Single<&mut Health, With<Player>>) {
+    player.current = player.max;
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    menus::Menu,
+    script::script::{ScriptEvent, ScriptEventQueue},
+    tests::{
+        bot::{Bot, add_bot},
+        harness::Harness,
+    },
+};
+
+/// Simulated time the bot gets to beat the whole campaign, boss included.
+const CAMPAIGN_TIME_BUDGET: Duration = Duration::from_secs(20 * 60);
+
+/// Plays the full script with the bot. Set `CAMPAIGN_GOD_MODE` to keep the player at full health.
+#[test]
+#[ignore = "plays the whole campaign, run with `cargo test -- --ignored`"]
+fn bot_finishes_campaign() {
+    let god_mode = std::env::var_os("CAMPAIGN_GOD_MODE").is_some();
+    let mut harness = Harness::new();
+    add_bot(&mut harness.app, Bot { god_mode });
+    harness.start_gameplay();
+
+    let finished = harness.step_until(CAMPAIGN_TIME_BUDGET, |world| {
+        *world.resource::<State<Menu>>().get() == Menu::Results
+    });
+
+    let queue = &harness.world().resource::<ScriptEventQueue>().queue;
+    assert!(
+        finished,
+        "campaign didn't finish within {CAMPAIGN_TIME_BUDGET:?}, stuck on {:?} with {} events left",
+        queue.front(),
+        queue.len()
+    );
+    assert!(matches!(queue.front(), Some(ScriptEvent::None)));
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+use crate::{
+    enemy::{
+        behaviour::StateMachine,
+        crowd::{AttackTokens, CrowdSettings},
+        slime::{SlimeKind, SlimeState},
+    },
+    tests::harness::Harness,
+};
+
+#[test]
+fn slimes_spawned_on_top_of_each_other_move_apart() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let slimes: Vec<Entity> = (0..2)
+        .map(|_| harness.spawn_slime(SlimeKind::Black, Vec3::new(600.0, -400.0, 0.0)))
+        .collect();
+    harness.step_for(Duration::from_millis(500));
+
+    let x = |harness: &Harness, entity: Entity| {
+        harness
+            .world()
+            .get::<Transform>(entity)
+            .unwrap()
+            .translation
+            .x
+    };
+    let gap = (x(&harness, slimes[0]) - x(&harness, slimes[1])).abs();
+    assert!(gap > 20.0, "slimes should have separated, gap is {gap}");
+}
+
+#[test]
+fn attack_tokens_limit_attacks_within_the_window() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+    harness.world_mut().insert_resource(CrowdSettings {
+        attack_tokens: 2,
+        attack_window: 0.5,
+        ..default()
+    });
+
+    let take = |harness: &mut Harness| {
+        harness
+            .world_mut()
+            .run_system_once(|mut tokens: AttackTokens| tokens.try_take())
+            .unwrap()
+    };
+    assert!(take(&mut harness));
+    assert!(take(&mut harness));
+    assert!(!take(&mut harness), "only two attacks fit in the window");
+
+    harness.step_for(Duration::from_millis(600));
+    assert!(
+        take(&mut harness),
+        "tokens come back once the window passes"
+    );
+}
+
+#[test]
+fn slimes_hopping_towards_a_jump_dont_need_attack_tokens() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+    harness.world_mut().insert_resource(CrowdSettings {
+        attack_tokens: 0,
+        ..default()
+    });
+
+    // One slime has to jump down from the left platform to reach the player, the other can hop
+    // straight at them.
+    let navigating = harness.spawn_slime(SlimeKind::Black, Vec3::new(-820.0, -100.0, 0.0));
+    let next_to_player = harness.player_translation() + Vec3::new(200.0, 0.0, 0.0);
+    let attacking = harness.spawn_slime(SlimeKind::Black, next_to_player);
+
+    let winding_up = |world: &World, slime: Entity| {
+        world
+            .get::<StateMachine<SlimeState>>(slime)
+            .unwrap()
+            .state()
+            == SlimeState::Windup
+    };
+    let mut attacked = false;
+    let hopped = harness.step_until(Duration::from_secs(4), |world| {
+        attacked |= winding_up(world, attacking);
+        winding_up(world, navigating)
+    });
+    assert!(
+        hopped,
+        "the slime on the left platform should head for the player"
+    );
+    assert!(
+        !attacked,
+        "without tokens the slime next to the player can't attack"
+    );
+}
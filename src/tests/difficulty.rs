@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{
+    difficulty::Difficulty,
+    enemy::{configs::ENEMY_SEPARATION_RADIUS, crowd::CrowdSettings, slime::SlimeKind},
+    health::Health,
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+#[test]
+fn harder_difficulties_give_enemies_more_health() {
+    let mut harness = Harness::new();
+    *harness.world_mut().resource_mut::<Difficulty>() = Difficulty::Nightmare;
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let slime = harness.spawn_slime(SlimeKind::Black, Vec3::new(600.0, -400.0, 0.0));
+    harness.step();
+
+    let health = harness.world().get::<Health>(slime).unwrap();
+    let expected =
+        Tuning::default().enemy.black_slime.health * Difficulty::Nightmare.scaling().enemy_health;
+    assert!(
+        (health.max - expected).abs() < 0.01,
+        "max health {}",
+        health.max
+    );
+    assert_eq!(health.current, health.max);
+
+    // The player is never scaled.
+    let player = harness.player();
+    let player_health = harness.world().get::<Health>(player).unwrap();
+    assert_eq!(player_health.max, Tuning::default().player.health);
+}
+
+#[test]
+fn story_difficulty_lets_fewer_enemies_attack_at_once() {
+    let mut harness = Harness::new();
+    *harness.world_mut().resource_mut::<Difficulty>() = Difficulty::Story;
+    harness.step();
+
+    let crowd = harness.world().resource::<CrowdSettings>();
+    let scaling = Difficulty::Story.scaling();
+    assert_eq!(crowd.attack_tokens, scaling.attack_tokens);
+    // They also keep further apart.
+    assert_eq!(
+        crowd.separation_radius,
+        ENEMY_SEPARATION_RADIUS * scaling.separation_radius
+    );
+    assert!(crowd.separation_radius > ENEMY_SEPARATION_RADIUS);
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use avian2d::prelude::Collider;
+use bevy::prelude::*;
+
+use crate::{
+    collision_layers::player_hit_boxes,
+    enemy::{
+        configs::ELITE_SHIELD,
+        elite::{Elite, EliteModifier},
+        slime::SlimeKind,
+    },
+    health::{Health, Shield, hitbox_prefab},
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+fn spawn_elite_slime(harness: &mut Harness, modifiers: Vec<EliteModifier>) -> Entity {
+    harness.idle_script();
+    let enemy = harness.spawn_slime(SlimeKind::Black, Vec3::new(400.0, -400.0, 0.0));
+    harness
+        .world_mut()
+        .entity_mut(enemy)
+        .insert(Elite(modifiers));
+    enemy
+}
+
+fn hit(harness: &mut Harness, enemy: Entity, damage: f32) {
+    let translation = harness.world().get::<Transform>(enemy).unwrap().translation;
+    harness.world_mut().spawn(hitbox_prefab(
+        Collider::circle(150.0),
+        player_hit_boxes(),
+        60.0,
+        damage,
+        Transform::from_translation(translation),
+    ));
+}
+
+#[test]
+fn shielded_elite_absorbs_damage_before_losing_health() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    let enemy = spawn_elite_slime(&mut harness, vec![EliteModifier::Shielded]);
+    harness.step();
+
+    hit(&mut harness, enemy, 5.0);
+    harness.step_for(Duration::from_millis(200));
+    let world = harness.world();
+    assert_eq!(
+        world.get::<Health>(enemy).unwrap().current,
+        Tuning::default().enemy.black_slime.health
+    );
+    assert_eq!(
+        world.get::<Shield>(enemy).unwrap().remaining,
+        ELITE_SHIELD - 5.0
+    );
+}
+
+#[test]
+fn explosive_elite_explodes_when_it_dies() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    let enemy = spawn_elite_slime(&mut harness, vec![EliteModifier::Explosive]);
+    harness.step();
+
+    hit(
+        &mut harness,
+        enemy,
+        Tuning::default().enemy.black_slime.health,
+    );
+    let exploded = harness.step_until(Duration::from_secs(1), |world| {
+        world
+            .query::<&Name>()
+            .iter(world)
+            .any(|name| name.as_str() == "Elite Explosion")
+    });
+    assert!(
+        exploded,
+        "an explosive elite should leave an explosion behind"
+    );
+}
+
+#[test]
+fn elite_shows_an_aura_for_every_modifier() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    let modifiers = vec![
+        EliteModifier::Hasted,
+        EliteModifier::Vampiric,
+        EliteModifier::Shielded,
+    ];
+    let enemy = spawn_elite_slime(&mut harness, modifiers);
+    harness.step();
+
+    let world = harness.world_mut();
+    let auras = world
+        .query::<(&ChildOf, &Name)>()
+        .iter(world)
+        .filter(|(parent, name)| parent.parent() == enemy && name.as_str() == "Elite Aura")
+        .count();
+    assert_eq!(auras, 3);
+}
@@ -0,0 +1,220 @@
+//! A headless [`App`] running the gameplay plugins with a fixed timestep.
+//!
+//! No window, renderer or audio device is created, so this runs fine on a CI box without a GPU.
+//! Asset resources are stubbed with handles that never finish loading.
+
+use std::time::Duration;
+
+use avian2d::PhysicsPlugins;
+use bevy::{
+    asset::AssetPlugin, ecs::system::RunSystemOnce, input::InputPlugin, prelude::*,
+    state::app::StatesPlugin, time::TimeUpdateStrategy,
+};
+
+use crate::{
+    animation, asset_tracking, configure_system_sets, difficulty,
+    enemy::{
+        self,
+        boss::LazerAssets,
+        caster::CasterAssets,
+        eye::EyeAssets,
+        imp::ImpAssets,
+        slime::{SlimeAssets, SlimeKind, slime},
+        wisp::WispAssets,
+    },
+    health,
+    level::{self, arena::LevelAssets},
+    menus, physics,
+    physics::creature::Grounded,
+    player::{
+        self,
+        attack::sound::AttackAssets,
+        character::{Player, PlayerAssets},
+        weapon::WeaponAssets,
+    },
+    projectile,
+    rng::{self, GameRng},
+    screens::{self, Screen, title::TitleAssets},
+    script::{
+        self,
+        script::{ScriptEvent, ScriptEventQueue},
+    },
+    tuning::{self, Tuning},
+};
+
+/// Matches the default [`Time<Fixed>`] rate, so every frame runs exactly one physics step.
+pub const FRAME: Duration = Duration::from_micros(15_625);
+
+/// Seed pinned for every test so enemy behaviour is repeatable.
+pub const TEST_SEED: u64 = 0xA5AD;
+
+pub struct Harness {
+    pub app: App,
+}
+
+impl Harness {
+    /// Builds the app and runs the startup schedules. The game sits on the loading screen
+    /// until [`Harness::start_gameplay`] is called.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            InputPlugin,
+            TransformPlugin,
+            AssetPlugin::default(),
+        ));
+        app.init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .init_asset::<Mesh>()
+            .init_asset::<ColorMaterial>()
+            .init_asset::<Font>()
+            .init_asset::<AudioSource>();
+
+        app.add_plugins((
+            asset_tracking::plugin,
+            PhysicsPlugins::default(),
+            physics::plugin,
+        ));
+        app.add_plugins((
+            menus::plugin,
+            screens::plugin,
+            health::plugin,
+            player::plugin,
+            projectile::plugin,
+            enemy::plugin,
+            animation::plugin,
+            level::arena::plugin,
+            rng::plugin,
+            script::plugin,
+            difficulty::plugin,
+            tuning::plugin,
+        ));
+        app.insert_resource(GameRng::new(Some(TEST_SEED)))
+            .insert_resource(Time::<Fixed>::from_duration(FRAME))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        configure_system_sets(&mut app);
+
+        stub_resource::<TitleAssets>(&mut app);
+        stub_resource::<LevelAssets>(&mut app);
+        stub_resource::<PlayerAssets>(&mut app);
+        stub_resource::<WeaponAssets>(&mut app);
+        stub_resource::<AttackAssets>(&mut app);
+        stub_resource::<SlimeAssets>(&mut app);
+        stub_resource::<EyeAssets>(&mut app);
+        stub_resource::<LazerAssets>(&mut app);
+        stub_resource::<ImpAssets>(&mut app);
+        stub_resource::<WispAssets>(&mut app);
+        stub_resource::<CasterAssets>(&mut app);
+
+        app.finish();
+        app.cleanup();
+        app.update();
+        Self { app }
+    }
+
+    /// Spawns the level and lets the player land on the main platform.
+    pub fn start_gameplay(&mut self) {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Gameplay);
+        self.step();
+        let landed = self.step_until(Duration::from_secs(3), |world| {
+            let player = player_entity(world);
+            world.get::<Grounded>(player).is_some()
+        });
+        assert!(landed, "player never landed after the level spawned");
+    }
+
+    pub fn step(&mut self) {
+        self.app.update();
+    }
+
+    pub fn step_for(&mut self, duration: Duration) {
+        for _ in 0..frames_in(duration) {
+            self.step();
+        }
+    }
+
+    /// Steps until `done` holds, giving up after `timeout` of simulated time.
+    /// Returns whether `done` was reached.
+    pub fn step_until(
+        &mut self,
+        timeout: Duration,
+        mut done: impl FnMut(&mut World) -> bool,
+    ) -> bool {
+        for _ in 0..frames_in(timeout) {
+            if done(self.app.world_mut()) {
+                return true;
+            }
+            self.step();
+        }
+        done(self.app.world_mut())
+    }
+
+    /// Queues an event to be read during the next [`Harness::step`].
+    pub fn send<E: Event>(&mut self, event: E) {
+        let mut event = Some(event);
+        self.app
+            .world_mut()
+            .run_system_once(move |mut writer: EventWriter<E>| {
+                if let Some(event) = event.take() {
+                    writer.write(event);
+                }
+            })
+            .expect("failed to send event");
+    }
+
+    /// Replaces the script with a long wait, so only the enemies a test spawns itself show up.
+    pub fn idle_script(&mut self) {
+        self.world_mut().resource_mut::<ScriptEventQueue>().queue =
+            vec![ScriptEvent::Wait(60.0)].into();
+    }
+
+    /// Spawns a slime straight away, skipping the spawn portal.
+    pub fn spawn_slime(&mut self, kind: SlimeKind, translation: Vec3) -> Entity {
+        let world = self.world_mut();
+        let assets = world.resource::<SlimeAssets>().clone();
+        let tuning = world.resource::<Tuning>().enemy.clone();
+        world.resource_scope(|world, mut rng: Mut<GameRng>| {
+            world
+                .spawn(slime(&assets, translation, kind, &mut rng.ai, &tuning))
+                .id()
+        })
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn player(&mut self) -> Entity {
+        player_entity(self.app.world_mut())
+    }
+
+    pub fn player_translation(&mut self) -> Vec3 {
+        let player = self.player();
+        self.world().get::<Transform>(player).unwrap().translation
+    }
+}
+
+pub fn player_entity(world: &mut World) -> Entity {
+    world
+        .query_filtered::<Entity, With<Player>>()
+        .single(world)
+        .expect("expected exactly one player")
+}
+
+fn frames_in(duration: Duration) -> u32 {
+    duration.as_micros().div_ceil(FRAME.as_micros()) as u32
+}
+
+/// Inserts the asset resource straight away instead of waiting for it to load.
+fn stub_resource<T: Resource + FromWorld>(app: &mut App) {
+    let stub = T::from_world(app.world_mut());
+    app.insert_resource(stub);
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use avian2d::prelude::Collider;
+use bevy::prelude::*;
+
+use crate::{
+    collision_layers::player_hit_boxes,
+    enemy::{
+        configs::{BLACK_SLIME, HIT_FLASH_COLOR},
+        hit_reaction::HitFlash,
+        slime::SlimeKind,
+    },
+    health::hitbox_prefab,
+    tests::harness::Harness,
+};
+
+#[test]
+fn hit_enemy_flashes_and_goes_back_to_normal() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let enemy = harness.spawn_slime(SlimeKind::Black, Vec3::new(400.0, -400.0, 0.0));
+    harness.step();
+    let base_scale = harness.world().get::<Transform>(enemy).unwrap().scale;
+    let base_size = harness.world().get::<Sprite>(enemy).unwrap().custom_size;
+
+    let translation = harness.world().get::<Transform>(enemy).unwrap().translation;
+    let hitbox = harness
+        .world_mut()
+        .spawn(hitbox_prefab(
+            Collider::circle(150.0),
+            player_hit_boxes(),
+            60.0,
+            1.0,
+            Transform::from_translation(translation - Vec3::X * 50.0),
+        ))
+        .id();
+    let flashed = harness.step_until(Duration::from_millis(200), |world| {
+        world.get::<HitFlash>(enemy).is_some()
+    });
+    assert!(flashed, "a hit enemy should flash");
+    harness.step();
+    assert_eq!(
+        harness.world().get::<Sprite>(enemy).unwrap().color,
+        HIT_FLASH_COLOR
+    );
+    // The squash only changes how the slime is drawn, so its colliders keep their size.
+    harness.step();
+    assert_ne!(
+        harness.world().get::<Sprite>(enemy).unwrap().custom_size,
+        base_size
+    );
+    assert_eq!(
+        harness.world().get::<Transform>(enemy).unwrap().scale,
+        base_scale
+    );
+
+    harness.world_mut().despawn(hitbox);
+    harness.step_for(Duration::from_millis(500));
+    let world = harness.world();
+    assert!(world.get::<HitFlash>(enemy).is_none());
+    assert_eq!(world.get::<Sprite>(enemy).unwrap().color, BLACK_SLIME.tint);
+    assert_eq!(world.get::<Transform>(enemy).unwrap().scale, base_scale);
+    assert_eq!(world.get::<Sprite>(enemy).unwrap().custom_size, base_size);
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{
+        configs::IMP_MELEE_SWING_DURATION,
+        imp::{ImpAssets, imp},
+    },
+    health::{Health, HitBox},
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+fn spawn_imp(harness: &mut Harness, translation: Vec3) -> Entity {
+    let world = harness.world_mut();
+    let assets = world.resource::<ImpAssets>().clone();
+    let tuning = world.resource::<Tuning>().enemy.clone();
+    world.spawn(imp(&assets, translation, &tuning)).id()
+}
+
+fn find_named(world: &mut World, name: &str) -> Option<Entity> {
+    world
+        .query::<(Entity, &Name)>()
+        .iter(world)
+        .find(|(_, entity_name)| entity_name.as_str() == name)
+        .map(|(entity, _)| entity)
+}
+
+#[test]
+fn imp_body_hurts_on_contact() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let enemy = spawn_imp(&mut harness, Vec3::new(400.0, -400.0, 0.0));
+    harness.step();
+
+    let world = harness.world_mut();
+    let contact_damage = world
+        .query::<(&ChildOf, &HitBox)>()
+        .iter(world)
+        .find(|(parent, _)| parent.parent() == enemy)
+        .map(|(_, hitbox)| hitbox.damage());
+    assert_eq!(
+        contact_damage,
+        Some(Tuning::default().enemy.imp.contact_damage)
+    );
+}
+
+#[test]
+fn imp_swing_hurts_the_player_and_goes_away() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    // Close enough to swing at the player, but not touching them.
+    let player = harness.player();
+    let translation = harness.player_translation() + Vec3::new(100.0, 0.0, 0.0);
+    spawn_imp(&mut harness, translation);
+
+    let mut swing = None;
+    let swung = harness.step_until(Duration::from_secs(1), |world| {
+        swing = find_named(world, "Imp Swing");
+        swing.is_some()
+    });
+    assert!(swung, "an imp next to the player should swing at them");
+    let swing = swing.unwrap();
+
+    let player_health = Tuning::default().player.health;
+    let hurt = harness.step_until(Duration::from_secs_f32(IMP_MELEE_SWING_DURATION), |world| {
+        world.get::<Health>(player).unwrap().current < player_health
+    });
+    assert!(hurt, "the swing should hurt the player");
+    assert_eq!(
+        harness.world().get::<Health>(player).unwrap().current,
+        player_health - Tuning::default().enemy.imp.melee_damage
+    );
+
+    harness.step_for(Duration::from_secs_f32(IMP_MELEE_SWING_DURATION + 0.05));
+    assert!(
+        harness.world().get_entity(swing).is_err(),
+        "the swing hitbox should be gone once the swing is over"
+    );
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{
+        configs::HEALTH_ORB_HEAL,
+        loot::{Loot, LootDrop, LootTable},
+        slime::SlimeKind,
+    },
+    health::{DeathEvent, Health},
+    tests::harness::Harness,
+};
+
+const ALWAYS_HEALTH_ORB: &[LootDrop] = &[LootDrop {
+    loot: Loot::HealthOrb,
+    chance: 1.0,
+}];
+
+#[test]
+fn health_orb_flies_to_the_player_and_heals_them() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let player = harness.player();
+    let translation = harness.player_translation() + Vec3::new(150.0, 50.0, 0.0);
+    harness
+        .world_mut()
+        .get_mut::<Health>(player)
+        .unwrap()
+        .current = 50.0;
+    let enemy = harness.spawn_slime(SlimeKind::Black, translation);
+    harness
+        .world_mut()
+        .entity_mut(enemy)
+        .insert(LootTable(ALWAYS_HEALTH_ORB));
+    harness.step();
+
+    harness.send(DeathEvent(enemy));
+    harness.step();
+    assert!(harness.world().get_entity(enemy).is_err());
+
+    let picked_up = harness.step_until(Duration::from_secs(2), |world| {
+        world.query::<&Loot>().iter(world).next().is_none()
+    });
+    assert!(picked_up, "the orb should be pulled in and picked up");
+    assert_eq!(
+        harness.world().get::<Health>(player).unwrap().current,
+        50.0 + HEALTH_ORB_HEAL
+    );
+}
@@ -0,0 +1,24 @@
+//! Headless simulation tests for gameplay systems.
+//!
+//! Run with `cargo test`. See [`harness::Harness`] for driving the game frame by frame.
+
+mod attack;
+mod boss;
+mod bot;
+mod campaign;
+mod crowd;
+mod difficulty;
+mod elite;
+pub mod harness;
+mod hit_reaction;
+mod imp;
+mod loot;
+mod movement;
+mod navigation;
+mod projectile;
+mod script;
+mod slime;
+mod spawning;
+mod stuck;
+mod tuning;
+mod wisp;
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use crate::{player::movement::movement::MovementAction, tests::harness::Harness, tuning::Tuning};
+
+#[test]
+fn jump_height_is_between_short_and_full_hop() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    let ground_y = harness.player_translation().y;
+
+    harness.send(MovementAction::JumpStart);
+    let mut apex = ground_y;
+    for _ in 0..128 {
+        harness.step();
+        apex = apex.max(harness.player_translation().y);
+    }
+    let height = apex - ground_y;
+
+    // Without holding jump the player can't go higher than a full floaty jump,
+    // and can't go lower than a jump under normal gravity.
+    let tuning = Tuning::default();
+    let (impulse, gravity) = (tuning.player.jump_impulse, tuning.physics.gravity);
+    let short_hop = impulse.powi(2) / (2.0 * gravity * tuning.player.gravity_scale);
+    let full_hop = impulse.powi(2) / (2.0 * gravity * tuning.player.jump_gravity_scale);
+    assert!(
+        height > 0.9 * short_hop && height < full_hop,
+        "jumped {height}, expected between {short_hop} and {full_hop}"
+    );
+}
+
+#[test]
+fn dash_covers_burst_plus_slide() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    let start_x = harness.player_translation().x;
+
+    harness.send(MovementAction::Dash);
+    harness.step_for(Duration::from_secs(1));
+    let distance = harness.player_translation().x - start_x;
+
+    // The player faces right on spawn. After the burst 40% of the dash speed is kept and damped away.
+    let tuning = Tuning::default().player;
+    let dash_speed = tuning.movement_speed * tuning.dash_speed_modifier;
+    let burst = dash_speed * tuning.dash_duration;
+    let slide = 0.4 * dash_speed / tuning.movement_damping;
+    assert!(
+        distance > 0.9 * burst && distance < 1.25 * (burst + slide),
+        "dashed {distance}, expected about {}",
+        burst + slide
+    );
+}
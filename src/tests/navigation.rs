@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::{enemy::navigation::NavGraph, tests::harness::Harness};
+
+#[test]
+fn nav_graph_links_the_main_platform_to_the_side_platforms() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+
+    let graph = harness.world().resource::<NavGraph>();
+    assert_eq!(graph.platforms.len(), 4, "one platform per ground collider");
+
+    let on_main = Vec2::new(0.0, -500.0);
+    let on_left = Vec2::new(-820.0, -150.0);
+    let up = graph
+        .next_hop(on_main, on_left, f32::INFINITY)
+        .expect("the left platform should be reachable from the main one");
+    assert!(up.landing.y > up.takeoff.y);
+    assert!(up.impulse.x < 0.0, "should jump towards the left platform");
+
+    let down = graph
+        .next_hop(on_left, on_main, f32::INFINITY)
+        .expect("the main platform should be reachable from the left one");
+    assert!(down.landing.y < down.takeoff.y);
+
+    assert!(
+        graph.next_hop(on_main, on_main, f32::INFINITY).is_none(),
+        "no jump needed on the same platform"
+    );
+    assert_eq!(graph.can_reach(on_main, on_left, f32::INFINITY), Some(true));
+    assert_eq!(graph.can_reach(on_main, on_main, 0.0), Some(true));
+    assert_eq!(
+        graph.can_reach(on_main, on_left, 0.0),
+        Some(false),
+        "too slow to make the jump"
+    );
+}
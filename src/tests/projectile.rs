@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use avian2d::prelude::{Collider, CollisionLayers, LinearVelocity};
+use bevy::prelude::*;
+
+use crate::{
+    collision_layers::player_hit_boxes,
+    enemy::{
+        caster::{CasterAssets, caster_bolt},
+        slime::SlimeKind,
+    },
+    health::{Health, hitbox_prefab},
+    player::weapon::WeaponHitbox,
+    projectile::{Projectile, ProjectileHitbox, REFLECT_SPEEDUP, projectile},
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+const BOLT_SPEED: f32 = 300.0;
+
+fn spawn_caster_bolt(harness: &mut Harness, translation: Vec3, velocity: Vec2) -> Entity {
+    let assets = harness.world().resource::<CasterAssets>().clone();
+    harness
+        .world_mut()
+        .spawn(caster_bolt(&assets, translation, velocity, 10.0))
+        .id()
+}
+
+fn bolt_layers(harness: &mut Harness, bolt: Entity) -> CollisionLayers {
+    let world = harness.world_mut();
+    let (_, layers) = world
+        .query_filtered::<(&ChildOf, &CollisionLayers), With<ProjectileHitbox>>()
+        .iter(world)
+        .find(|(parent, _)| parent.parent() == bolt)
+        .expect("bolt should have a hitbox");
+    *layers
+}
+
+#[test]
+fn projectile_despawns_when_it_hits_the_ground() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    // Above the main platform, with plenty of lifetime left when it gets there.
+    let bolt = harness
+        .world_mut()
+        .spawn((
+            Transform::from_xyz(0.0, -400.0, 0.0),
+            projectile(Projectile::new(10.0), Vec2::NEG_Y * 300.0),
+        ))
+        .id();
+    harness.step();
+    assert!(harness.world().get_entity(bolt).is_ok());
+
+    let despawned = harness.step_until(Duration::from_secs(2), |world| {
+        world.get_entity(bolt).is_err()
+    });
+    assert!(despawned, "projectile should despawn on hitting the ground");
+}
+
+#[test]
+fn weapon_reflects_caster_bolt_into_enemies() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    // The bolt flies at the player from the right, with a slime waiting behind it.
+    let player = harness.player_translation();
+    let slime = harness.spawn_slime(SlimeKind::Black, player + Vec3::new(500.0, 0.0, 0.0));
+    let start = player + Vec3::new(200.0, 0.0, 0.0);
+    let bolt = spawn_caster_bolt(&mut harness, start, Vec2::NEG_X * BOLT_SPEED);
+    let weapon = harness
+        .world_mut()
+        .spawn((
+            hitbox_prefab(
+                Collider::rectangle(100.0, 140.0),
+                player_hit_boxes(),
+                0.5,
+                10.0,
+                Transform::from_translation(start),
+            ),
+            WeaponHitbox,
+        ))
+        .id();
+
+    let reflected = harness.step_until(Duration::from_millis(200), |world| {
+        world.get::<LinearVelocity>(bolt).unwrap().x > 0.0
+    });
+    assert!(reflected, "the weapon should knock the bolt back");
+    harness.world_mut().despawn(weapon);
+
+    let velocity = harness.world().get::<LinearVelocity>(bolt).unwrap().0;
+    assert!(
+        (velocity.length() - BOLT_SPEED * REFLECT_SPEEDUP).abs() < 0.01,
+        "reflected speed {}",
+        velocity.length()
+    );
+    assert!(!harness.world().get::<Projectile>(bolt).unwrap().reflectable);
+    assert_eq!(bolt_layers(&mut harness, bolt), player_hit_boxes());
+
+    let slime_health = Tuning::default().enemy.black_slime.health;
+    let hurt = harness.step_until(Duration::from_secs(2), |world| {
+        world.get::<Health>(slime).unwrap().current < slime_health
+    });
+    assert!(hurt, "the reflected bolt should hurt the slime");
+
+    let despawned = harness.step_until(Duration::from_millis(100), |world| {
+        world.get_entity(bolt).is_err()
+    });
+    assert!(
+        despawned,
+        "a bolt that doesn't pierce should despawn after a hit"
+    );
+}
+
+#[test]
+fn caster_bolt_despawns_after_hurting_the_player() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let player = harness.player();
+    let start = harness.player_translation() + Vec3::new(150.0, 0.0, 0.0);
+    let bolt = spawn_caster_bolt(&mut harness, start, Vec2::NEG_X * BOLT_SPEED);
+
+    let player_health = Tuning::default().player.health;
+    let hurt = harness.step_until(Duration::from_secs(1), |world| {
+        world.get::<Health>(player).unwrap().current < player_health
+    });
+    assert!(hurt, "the bolt should hurt the player");
+
+    let despawned = harness.step_until(Duration::from_millis(100), |world| {
+        world.get_entity(bolt).is_err()
+    });
+    assert!(
+        despawned,
+        "a bolt that doesn't pierce should despawn after a hit"
+    );
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{
+        boss::BossController,
+        configs::{ENEMY_SPAWN_TELEGRAPH, SPAWN_TELEGRAPH_JITTER},
+    },
+    script::script::{Dialogue, Enemy, ResetScript, ScriptEvent, ScriptEventQueue, ScriptPosition},
+    tests::harness::Harness,
+};
+
+#[test]
+fn script_positions_parse_as_indices_or_labels() {
+    assert_eq!("12".parse(), Ok(ScriptPosition::Index(12)));
+    assert_eq!(
+        "boss".parse(),
+        Ok(ScriptPosition::Label("boss".to_string()))
+    );
+    assert!(ScriptPosition::Label("boss".to_string()).index().is_some());
+    assert_eq!(ScriptPosition::Label("nowhere".to_string()).index(), None);
+}
+
+#[test]
+fn resetting_the_script_to_a_label_starts_there() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+
+    harness.send(ResetScript {
+        start_at: ScriptPosition::Label("boss".to_string()),
+        ..default()
+    });
+    harness.step();
+
+    let queue = &harness.world().resource::<ScriptEventQueue>().queue;
+    assert!(
+        matches!(queue.front(), Some(ScriptEvent::Dialogue("Asad", _))),
+        "script starts on {:?}",
+        queue.front()
+    );
+    let mut dialogue = harness
+        .world_mut()
+        .query_filtered::<&Visibility, With<Dialogue>>();
+    assert_eq!(
+        *dialogue.single(harness.world()).unwrap(),
+        Visibility::Inherited
+    );
+}
+
+#[test]
+fn starting_partway_through_a_fight_spawns_its_enemies() {
+    let mut harness = Harness::new();
+    let boss_spawn = harness
+        .world()
+        .resource::<ScriptEventQueue>()
+        .queue
+        .iter()
+        .position(|event| matches!(event, ScriptEvent::Spawn(Enemy::Boss, ..)))
+        .unwrap();
+    harness.start_gameplay();
+
+    harness.send(ResetScript {
+        start_at: ScriptPosition::Index(boss_spawn + 1),
+        ..default()
+    });
+    harness.step_for(Duration::from_secs_f32(
+        ENEMY_SPAWN_TELEGRAPH + SPAWN_TELEGRAPH_JITTER + 0.1,
+    ));
+
+    let world = harness.world_mut();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<BossController>>()
+            .iter(world)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn skipping_dialogue_leaves_none_in_the_script() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+
+    harness.send(ResetScript {
+        start_at: ScriptPosition::Label("boss".to_string()),
+        skip_dialogue: true,
+    });
+    harness.step();
+
+    let queue = &harness.world().resource::<ScriptEventQueue>().queue;
+    assert!(
+        !queue
+            .iter()
+            .any(|event| matches!(event, ScriptEvent::Dialogue(..)))
+    );
+}
+
+#[test]
+fn skipping_never_drops_the_end_of_the_script() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    let mut script = harness.world_mut().resource_mut::<ScriptEventQueue>();
+    script.queue = vec![ScriptEvent::Wait(60.0), ScriptEvent::None].into();
+
+    assert!(matches!(script.skip(), Some(ScriptEvent::Wait(_))));
+    assert!(script.skip().is_none());
+    assert!(matches!(script.queue.front(), Some(ScriptEvent::None)));
+
+    // An empty queue mustn't hang the frame either.
+    script.queue.clear();
+    harness.step();
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use avian2d::prelude::Collider;
+use bevy::prelude::*;
+
+use crate::{
+    collision_layers::player_hit_boxes,
+    enemy::slime::{SlimeController, SlimeKind},
+    health::{Health, hitbox_prefab},
+    script::script::{ScriptEvent, ScriptEventQueue},
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+#[test]
+fn slime_dies_and_releases_wait_for_slimes_dead() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.world_mut().resource_mut::<ScriptEventQueue>().queue =
+        vec![ScriptEvent::WaitForSlimesDead, ScriptEvent::Wait(60.0)].into();
+
+    let enemy = harness.spawn_slime(SlimeKind::Black, Vec3::new(400.0, -400.0, 0.0));
+    harness.step_for(Duration::from_millis(500));
+
+    assert_eq!(
+        harness.world().get::<Health>(enemy).unwrap().current,
+        Tuning::default().enemy.black_slime.health
+    );
+    let queue = &harness.world().resource::<ScriptEventQueue>().queue;
+    assert!(matches!(
+        queue.front(),
+        Some(ScriptEvent::WaitForSlimesDead)
+    ));
+
+    let translation = harness.world().get::<Transform>(enemy).unwrap().translation;
+    harness.world_mut().spawn(hitbox_prefab(
+        Collider::circle(150.0),
+        player_hit_boxes(),
+        0.5,
+        Tuning::default().enemy.black_slime.health,
+        Transform::from_translation(translation),
+    ));
+    let died = harness.step_until(Duration::from_secs(1), |world| {
+        world.get_entity(enemy).is_err()
+    });
+    assert!(died, "slime should die from a hit worth its whole health");
+
+    harness.step();
+    let queue = &harness.world().resource::<ScriptEventQueue>().queue;
+    assert!(matches!(queue.front(), Some(ScriptEvent::Wait(_))));
+}
+
+#[test]
+fn splitting_slime_splits_in_two_when_it_dies() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let enemy = harness.spawn_slime(SlimeKind::Splitting, Vec3::new(400.0, -400.0, 0.0));
+    harness.step_for(Duration::from_millis(500));
+
+    let translation = harness.world().get::<Transform>(enemy).unwrap().translation;
+    harness.world_mut().spawn(hitbox_prefab(
+        Collider::circle(150.0),
+        player_hit_boxes(),
+        0.5,
+        Tuning::default().enemy.splitting_slime.health,
+        Transform::from_translation(translation),
+    ));
+    let died = harness.step_until(Duration::from_secs(1), |world| {
+        world.get_entity(enemy).is_err()
+    });
+    assert!(
+        died,
+        "splitting slime should die from a hit worth its whole health"
+    );
+
+    let world = harness.world_mut();
+    let kinds: Vec<SlimeKind> = world
+        .query::<&SlimeController>()
+        .iter(world)
+        .map(SlimeController::kind)
+        .collect();
+    assert_eq!(kinds, vec![SlimeKind::Splitling; 2]);
+}
@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use avian2d::prelude::{ColliderDisabled, LinearVelocity};
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{
+        behaviour::StateMachine,
+        configs::{ENEMY_MATERIALIZE_DURATION, ENEMY_SPAWN_TELEGRAPH, SPAWN_TELEGRAPH_JITTER},
+        elite::{Elite, EliteModifier},
+        slime::{SlimeController, SlimeState},
+    },
+    health::HitBox,
+    script::{
+        script::{Enemy, ResetScript, ScriptEvent, ScriptEventQueue},
+        spawning::{Materializing, SpawnPortal},
+    },
+    tests::harness::Harness,
+};
+
+fn queue_slime_spawn(harness: &mut Harness) {
+    harness.world_mut().resource_mut::<ScriptEventQueue>().queue = vec![
+        ScriptEvent::Spawn(Enemy::BlackSlime, Vec2::new(500.0, 100.0), &[]),
+        ScriptEvent::Wait(60.0),
+    ]
+    .into();
+}
+
+fn count<F: bevy::ecs::query::QueryFilter>(harness: &mut Harness) -> usize {
+    let world = harness.world_mut();
+    world.query_filtered::<(), F>().iter(world).count()
+}
+
+fn slime_hitbox_disabled(harness: &mut Harness) -> bool {
+    let world = harness.world_mut();
+    let slime = world
+        .query_filtered::<&Children, With<SlimeController>>()
+        .single(world)
+        .unwrap()
+        .to_vec();
+    slime.into_iter().any(|child| {
+        world.get::<HitBox>(child).is_some() && world.get::<ColliderDisabled>(child).is_some()
+    })
+}
+
+#[test]
+fn enemies_come_through_a_portal_and_materialize_harmless() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    queue_slime_spawn(&mut harness);
+    harness.step();
+
+    assert_eq!(count::<With<SpawnPortal>>(&mut harness), 1);
+    assert_eq!(count::<With<SlimeController>>(&mut harness), 0);
+    // Scripted spawns land exactly where the script says.
+    let world = harness.world_mut();
+    let portal = world
+        .query_filtered::<&Transform, With<SpawnPortal>>()
+        .single(world)
+        .unwrap();
+    assert_eq!(portal.translation, Vec3::new(500.0, 100.0, 0.0));
+
+    harness.step_for(Duration::from_secs_f32(
+        ENEMY_SPAWN_TELEGRAPH + SPAWN_TELEGRAPH_JITTER + 0.05,
+    ));
+    assert_eq!(count::<With<SpawnPortal>>(&mut harness), 0);
+    assert_eq!(count::<With<Materializing>>(&mut harness), 1);
+    assert!(
+        slime_hitbox_disabled(&mut harness),
+        "a materializing slime shouldn't be able to hurt anyone"
+    );
+
+    harness.step_for(Duration::from_secs_f32(ENEMY_MATERIALIZE_DURATION + 0.1));
+    assert_eq!(count::<With<Materializing>>(&mut harness), 0);
+    assert!(!slime_hitbox_disabled(&mut harness));
+}
+
+fn slime_machine(harness: &mut Harness) -> (SlimeState, f32) {
+    let world = harness.world_mut();
+    let machine = world
+        .query_filtered::<&StateMachine<SlimeState>, With<SlimeController>>()
+        .single(world)
+        .unwrap();
+    (machine.state(), machine.elapsed())
+}
+
+#[test]
+fn materializing_enemies_hold_still_until_they_are_there() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    queue_slime_spawn(&mut harness);
+    let spawned = harness.step_until(Duration::from_secs(2), |world| {
+        world
+            .query_filtered::<(), With<Materializing>>()
+            .iter(world)
+            .next()
+            .is_some()
+    });
+    assert!(spawned, "the slime should come through its portal");
+    let spawned = slime_machine(&mut harness);
+
+    harness.step_for(Duration::from_secs_f32(ENEMY_MATERIALIZE_DURATION * 0.8));
+    assert_eq!(count::<With<Materializing>>(&mut harness), 1);
+    assert_eq!(
+        slime_machine(&mut harness),
+        spawned,
+        "a materializing slime's AI shouldn't run"
+    );
+    let world = harness.world_mut();
+    let velocity = world
+        .query_filtered::<&LinearVelocity, With<SlimeController>>()
+        .single(world)
+        .unwrap();
+    assert_eq!(velocity.x, 0.0);
+
+    harness.step_for(Duration::from_secs_f32(
+        ENEMY_MATERIALIZE_DURATION * 0.2 + 0.1,
+    ));
+    assert_eq!(count::<With<Materializing>>(&mut harness), 0);
+    assert!(slime_machine(&mut harness).1 > spawned.1);
+}
+
+#[test]
+fn resetting_the_script_cancels_pending_spawns() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    queue_slime_spawn(&mut harness);
+    harness.step();
+    assert_eq!(count::<With<SpawnPortal>>(&mut harness), 1);
+
+    harness.send(ResetScript::default());
+    harness.step_for(Duration::from_secs_f32(
+        ENEMY_SPAWN_TELEGRAPH + SPAWN_TELEGRAPH_JITTER + 0.1,
+    ));
+    assert_eq!(count::<With<SpawnPortal>>(&mut harness), 0);
+    assert_eq!(count::<With<SlimeController>>(&mut harness), 0);
+}
+
+#[test]
+fn resetting_the_script_removes_live_enemies() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    queue_slime_spawn(&mut harness);
+    harness.step_for(Duration::from_secs_f32(
+        ENEMY_SPAWN_TELEGRAPH + SPAWN_TELEGRAPH_JITTER + 0.1,
+    ));
+    assert_eq!(count::<With<SlimeController>>(&mut harness), 1);
+
+    harness.send(ResetScript::default());
+    harness.step();
+    assert_eq!(count::<With<SlimeController>>(&mut harness), 0);
+}
+
+#[test]
+fn waiting_for_the_boss_includes_its_portal() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.world_mut().resource_mut::<ScriptEventQueue>().queue = vec![
+        ScriptEvent::Spawn(Enemy::Boss, Vec2::new(0.0, 200.0), &[]),
+        ScriptEvent::WaitForBossDead,
+        ScriptEvent::Wait(60.0),
+    ]
+    .into();
+    harness.step();
+
+    assert_eq!(count::<With<SpawnPortal>>(&mut harness), 1);
+    assert!(matches!(
+        harness.world().resource::<ScriptEventQueue>().queue.front(),
+        Some(ScriptEvent::WaitForBossDead)
+    ));
+}
+
+#[test]
+fn only_spawns_with_modifiers_make_elites() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.world_mut().resource_mut::<ScriptEventQueue>().queue = vec![
+        ScriptEvent::Spawn(Enemy::BlackSlime, Vec2::new(-500.0, 100.0), &[]),
+        ScriptEvent::Spawn(
+            Enemy::BlackSlime,
+            Vec2::new(500.0, 100.0),
+            &[EliteModifier::Shielded],
+        ),
+        ScriptEvent::Wait(60.0),
+    ]
+    .into();
+    harness.step_for(Duration::from_secs_f32(
+        ENEMY_SPAWN_TELEGRAPH + SPAWN_TELEGRAPH_JITTER + 0.1,
+    ));
+
+    assert_eq!(count::<With<SlimeController>>(&mut harness), 2);
+    assert_eq!(count::<With<Elite>>(&mut harness), 1);
+}
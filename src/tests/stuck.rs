@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    collision_layers::GameLayer,
+    enemy::{configs::STUCK_RELOCATE_SECONDS, slime::SlimeKind},
+    tests::harness::Harness,
+};
+
+#[test]
+fn slime_pacing_on_an_unreachable_ledge_gets_relocated() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    // Far too far out for any jump to get back to the arena.
+    harness.world_mut().spawn((
+        Name::new("Unreachable Ledge"),
+        Transform::from_xyz(4000.0, -600.0, 0.0),
+        RigidBody::Static,
+        Collider::rectangle(600.0, 100.0),
+        CollisionLayers::new(GameLayer::Ground, LayerMask::ALL),
+    ));
+    let slime = harness.spawn_slime(SlimeKind::Black, Vec3::new(4000.0, -450.0, 0.0));
+
+    let relocated = harness.step_until(
+        Duration::from_secs_f32(STUCK_RELOCATE_SECONDS + 1.0),
+        |world| world.get::<Transform>(slime).unwrap().translation.x < 1000.0,
+    );
+    assert!(
+        relocated,
+        "a slime that can't reach the player should be put back in the arena"
+    );
+}
@@ -0,0 +1,27 @@
+use avian2d::prelude::Gravity;
+
+use crate::{tests::harness::Harness, tuning::Tuning};
+
+#[test]
+fn retuned_gravity_applies_to_the_physics_world() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    assert_eq!(
+        harness.world().resource::<Gravity>().0.y,
+        -Tuning::default().physics.gravity
+    );
+
+    harness.world_mut().resource_mut::<Tuning>().physics.gravity = 900.0;
+    harness.step();
+    assert_eq!(harness.world().resource::<Gravity>().0.y, -900.0);
+}
+
+#[test]
+fn exported_tuning_reads_back_the_same() {
+    let mut tuning = Tuning::default();
+    tuning.player.movement_speed = 612.5;
+    tuning.attack.scale_increase_factor = 1.35;
+
+    let exported = tuning.to_ron().unwrap();
+    assert_eq!(ron::from_str::<Tuning>(&exported).unwrap(), tuning);
+}
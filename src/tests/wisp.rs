@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use avian2d::prelude::Collider;
+use bevy::prelude::*;
+
+use crate::{
+    collision_layers::player_hit_boxes,
+    enemy::wisp::{WispAssets, wisp},
+    health::{DownwardAttack, Health, hitbox_prefab},
+    rng::GameRng,
+    tests::harness::Harness,
+    tuning::Tuning,
+};
+
+#[test]
+fn wisp_only_takes_damage_from_upward_and_horizontal_attacks() {
+    let mut harness = Harness::new();
+    harness.start_gameplay();
+    harness.idle_script();
+
+    let world = harness.world_mut();
+    let assets = world.resource::<WispAssets>().clone();
+    let tuning = world.resource::<Tuning>().enemy.clone();
+    let enemy = world.resource_scope(|world, mut rng: Mut<GameRng>| {
+        world
+            .spawn(wisp(
+                &assets,
+                Vec3::new(400.0, 0.0, 0.0),
+                &mut rng.ai,
+                &tuning,
+            ))
+            .id()
+    });
+    harness.step();
+
+    let translation = harness.world().get::<Transform>(enemy).unwrap().translation;
+    let downward = harness
+        .world_mut()
+        .spawn((
+            hitbox_prefab(
+                Collider::circle(150.0),
+                player_hit_boxes(),
+                0.5,
+                5.0,
+                Transform::from_translation(translation + Vec3::Y * 50.0),
+            ),
+            DownwardAttack,
+        ))
+        .id();
+    harness.step_for(Duration::from_millis(200));
+    assert_eq!(
+        harness.world().get::<Health>(enemy).unwrap().current,
+        Tuning::default().enemy.wisp.health,
+        "downward attacks shouldn't hurt a wisp"
+    );
+
+    harness
+        .world_mut()
+        .entity_mut(downward)
+        .remove::<DownwardAttack>();
+    let hurt = harness.step_until(Duration::from_secs(1), |world| {
+        world.get::<Health>(enemy).unwrap().current < Tuning::default().enemy.wisp.health
+    });
+    assert!(hurt, "other attacks should hurt a wisp");
+}
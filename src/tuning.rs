@@ -0,0 +1,269 @@
+//! Balance numbers, loaded from `assets/tuning.ron` into the [`Tuning`] resource.
+//!
+//! Systems read [`Res<Tuning>`] instead of constants, so with the `file_watcher` feature
+//! (on in native dev builds) edits to the file show up in the running game. Values that are
+//! baked into entities when they spawn, like health, only apply to entities spawned afterwards.
+
+use std::error::Error;
+
+use avian2d::{math::Vector, prelude::Gravity};
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Tuning>();
+    app.init_asset::<Tuning>()
+        .init_asset_loader::<TuningLoader>()
+        .init_resource::<Tuning>();
+    app.add_systems(Startup, load_tuning);
+    app.add_systems(
+        PreUpdate,
+        (
+            apply_tuning_changes.run_if(resource_exists::<TuningHandle>),
+            apply_gravity.run_if(resource_changed::<Tuning>),
+        )
+            .chain(),
+    );
+}
+
+/// Relative to the `assets` folder.
+pub const TUNING_PATH: &str = "tuning.ron";
+
+/// Kept at the top of the tuning file when it's written back out.
+const TUNING_HEADER: &str = "\
+// Balance numbers, read into the `Tuning` resource.
+// Native dev builds pick up changes to this file while the game is running.
+";
+
+#[derive(Asset, Resource, Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[reflect(Resource)]
+pub struct Tuning {
+    pub player: PlayerTuning,
+    pub attack: AttackTuning,
+    pub physics: PhysicsTuning,
+    pub enemy: EnemyTuning,
+}
+
+impl Default for Tuning {
+    /// The tuning file as it was at compile time. Used until the asset finishes loading, and
+    /// by tests.
+    fn default() -> Self {
+        ron::from_str(include_str!("../assets/tuning.ron")).expect("invalid bundled tuning file")
+    }
+}
+
+impl Tuning {
+    /// The contents of a tuning file with these values.
+    pub fn to_ron(&self) -> ron::Result<String> {
+        let body = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        Ok(format!("{TUNING_HEADER}{body}\n"))
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PlayerTuning {
+    pub movement_speed: f32,
+    /// Multiplies [`PlayerTuning::movement_speed`] while dashing.
+    pub dash_speed_modifier: f32,
+    pub jump_impulse: f32,
+    pub movement_damping: f32,
+    pub gravity_scale: f32,
+    /// Gravity scale while the jump button is held.
+    pub jump_gravity_scale: f32,
+    /// How long holding the jump button keeps the jump floaty.
+    pub jump_duration: f32,
+    pub dash_duration: f32,
+    pub dash_cooldown: f32,
+    /// How long the player can still jump after walking off a ledge.
+    pub coyote_time: f32,
+    pub health: f32,
+}
+
+/// How the weapon's chain reaction ("fury") speeds up and slows down.
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct AttackTuning {
+    /// Seconds from the button press to the first attack.
+    pub initial_cooldown: f32,
+    pub minimum_cooldown: f32,
+    /// How long the weapon stays ready for the next attack after a hit.
+    pub attack_period: f32,
+    /// How long the weapon cools down before the chain is lost.
+    pub grace_period: f32,
+    pub initial_extend_scale: f32,
+    pub minimum_extend_scale: f32,
+    pub scale_increase_factor: f32,
+    pub scale_decrease_factor: f32,
+    pub cooldown_increase_factor: f32,
+    pub cooldown_decrease_factor: f32,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PhysicsTuning {
+    pub gravity: f32,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct EnemyTuning {
+    /// How high slimes and imps can jump, which also decides what the navigation graph links.
+    pub jump_impulse: f32,
+    pub boss_health: f32,
+    pub boss: BossTuning,
+    pub black_slime: SlimeTuning,
+    pub red_slime: SlimeTuning,
+    pub splitting_slime: SlimeTuning,
+    pub splitling_slime: SlimeTuning,
+    pub bouncer_slime: SlimeTuning,
+    pub armored_slime: SlimeTuning,
+    pub imp: ImpTuning,
+    pub wisp: WispTuning,
+    pub caster: CasterTuning,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SlimeTuning {
+    pub health: f32,
+    pub jump_attack_cooldown: f32,
+    pub max_x_velocity: f32,
+    /// Contact damage.
+    pub damage: f32,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ImpTuning {
+    pub health: f32,
+    pub max_x_velocity: f32,
+    pub jump_attack_cooldown: f32,
+    pub melee_attack_cooldown: f32,
+    pub melee_damage: f32,
+    /// Contact damage, which is what lands a jump attack.
+    pub contact_damage: f32,
+    /// Jump at the player when they are at least this much higher or lower...
+    pub jump_attack_min_height_diff: f32,
+    /// ...or at least this far away horizontally.
+    pub jump_attack_min_distance: f32,
+    /// Swing at the player when they are within this height...
+    pub melee_max_height_diff: f32,
+    /// ...and this horizontal distance.
+    pub melee_max_distance: f32,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct WispTuning {
+    pub health: f32,
+    pub contact_damage: f32,
+    pub max_speed: f32,
+    pub swoop_speed: f32,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CasterTuning {
+    pub health: f32,
+    pub max_x_velocity: f32,
+    pub bolt_speed: f32,
+    pub bolt_damage: f32,
+    /// The caster backs off when the player is closer than this.
+    pub min_distance: f32,
+    /// The caster walks towards the player when they are further than this.
+    pub max_distance: f32,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BossTuning {
+    /// One per boss phase, in order.
+    pub phases: Vec<BossPhaseTuning>,
+    pub beam: LazerAttackTuning,
+    pub ring: LazerAttackTuning,
+    pub sky_rain: LazerAttackTuning,
+    pub sky_lazer_damage: f32,
+}
+
+impl BossTuning {
+    /// The timings of the phase at `index`, or of the last phase if there are fewer.
+    pub fn phase(&self, index: usize) -> &BossPhaseTuning {
+        self.phases
+            .get(index)
+            .or(self.phases.last())
+            .expect("the boss tuning lists no phases")
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct BossPhaseTuning {
+    /// How long the boss recovers after each attack.
+    pub time_between_attacks: f32,
+    /// How long flying over to the other side of the arena takes.
+    pub time_to_reposition: f32,
+}
+
+/// The timings of one kind of lazer attack.
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct LazerAttackTuning {
+    /// How long the boss warns the player before the attack starts.
+    pub telegraph: f32,
+    /// From the start of the attack until the boss recovers.
+    pub duration: f32,
+    /// How long each lazer shows as an aiming line or landing marker before it can hurt.
+    pub windup: f32,
+    /// How long each lazer hurts for once it fires.
+    pub lazer_duration: f32,
+    /// Spawn the lazers again every this many seconds until the attack ends, before difficulty
+    /// scaling.
+    pub spawn_every: Option<f32>,
+}
+
+#[derive(Default)]
+struct TuningLoader;
+
+impl AssetLoader for TuningLoader {
+    type Asset = Tuning;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Tuning, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// Keeps the tuning file loaded, so it gets reloaded when it changes.
+#[derive(Resource)]
+struct TuningHandle(Handle<Tuning>);
+
+fn load_tuning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuningHandle(asset_server.load(TUNING_PATH)));
+}
+
+fn apply_tuning_changes(
+    mut asset_events: EventReader<AssetEvent<Tuning>>,
+    handle: Res<TuningHandle>,
+    assets: Res<Assets<Tuning>>,
+    mut tuning: ResMut<Tuning>,
+) {
+    for event in asset_events.read() {
+        if !event.is_loaded_with_dependencies(&handle.0) && !event.is_modified(&handle.0) {
+            continue;
+        }
+        if let Some(loaded) = assets.get(&handle.0) {
+            if tuning.set_if_neq(loaded.clone()) {
+                info!("Applied tuning from {TUNING_PATH}");
+            }
+        }
+    }
+}
+
+fn apply_gravity(tuning: Res<Tuning>, mut gravity: ResMut<Gravity>) {
+    gravity.0 = Vector::NEG_Y * tuning.physics.gravity;
+}